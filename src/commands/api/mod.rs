@@ -0,0 +1,221 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Context, Result};
+use clap::Parser;
+use serde_json::{Map, Value};
+use tokio::io::AsyncReadExt;
+
+use crate::state::State;
+use crate::store::hopfile::HopFile;
+use crate::utils::output::OutputFormat;
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Make an authenticated request to the Hop API",
+    long_about = "Make an authenticated request to the Hop API\n\nThe placeholders `{project}` and `{deployment}` in the path are replaced with the current project and the deployment of the Hopfile in the current directory."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "HTTP method of the request, e.g. `GET` or `POST`")]
+    pub method: String,
+
+    #[clap(help = "Path of the endpoint, e.g. `/projects/{project}/secrets`")]
+    pub path: String,
+
+    #[clap(
+        short = 'f',
+        long = "field",
+        help = "Add a string field to the JSON body, can be repeated",
+        value_parser = parse_field
+    )]
+    pub fields: Vec<(String, String)>,
+
+    #[clap(
+        long,
+        help = "File to use as the request body, use `-` to read from stdin",
+        conflicts_with = "fields"
+    )]
+    pub input: Option<PathBuf>,
+
+    #[clap(
+        short,
+        long,
+        help = "Add a query parameter, can be repeated",
+        value_parser = parse_field
+    )]
+    pub query: Vec<(String, String)>,
+
+    #[clap(long, help = "Only print the `data` of the response")]
+    pub unwrap: bool,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    let path = fill_placeholders(&options.path, &state).await?;
+    let method = options.method.to_uppercase();
+
+    let body = if let Some(input) = &options.input {
+        Some(read_input(input).await?)
+    } else if !options.fields.is_empty() {
+        Some(serde_json::to_string(&fields_to_body(&options.fields))?)
+    } else {
+        None
+    };
+
+    let query = options
+        .query
+        .iter()
+        .map(|(key, value)| [key.as_str(), value.as_str()])
+        .collect::<Vec<_>>();
+    let query = query.iter().collect::<Vec<_>>();
+
+    let response = state
+        .http
+        .send_with_query(
+            &method,
+            &path,
+            (!query.is_empty()).then_some(&query),
+            body.map(|body| (body.into(), "application/json")),
+        )
+        .await?;
+
+    let status = response.status();
+
+    if !status.is_success() {
+        return state
+            .http
+            .handle_error::<()>(response, status)
+            .await
+            .map(|_| ());
+    }
+
+    let text = response.text().await?;
+
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    let mut value = serde_json::from_str::<Value>(&text).context("Response is not valid JSON")?;
+
+    if options.unwrap {
+        value = value
+            .get_mut("data")
+            .map(Value::take)
+            .context("Response does not contain `data`")?;
+    }
+
+    let output = match state.output {
+        OutputFormat::Table => OutputFormat::Json,
+        output => output,
+    };
+
+    println!("{}", output.serialize(&value)?.unwrap_or_default());
+
+    Ok(())
+}
+
+/// Replaces `{project}` and `{deployment}`, an explicit `--project`
+/// takes precedence over the project of the Hopfile
+async fn fill_placeholders(path: &str, state: &State) -> Result<String> {
+    let mut path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+
+    if !path.contains("{project}") && !path.contains("{deployment}") {
+        return Ok(path);
+    }
+
+    let explicit = state.explicit_project;
+
+    // `{deployment}` needs the Hopfile, for `{project}` it is optional
+    let hopfile = if path.contains("{deployment}") {
+        HopFile::find_current().await?
+    } else if explicit {
+        None
+    } else {
+        HopFile::find_current().await.unwrap_or_else(|error| {
+            log::debug!("Using the project of the context, the Hopfile is invalid: {error}");
+
+            None
+        })
+    };
+
+    if path.contains("{project}") {
+        let project = match &hopfile {
+            Some(hopfile) if !explicit && !hopfile.config.project_id.is_empty() => {
+                hopfile.config.project_id.clone()
+            }
+            _ => state.ctx.current_project_error()?.id,
+        };
+
+        path = path.replace("{project}", &project);
+    }
+
+    if path.contains("{deployment}") {
+        let Some(hopfile) = hopfile else {
+            bail!(
+                "No Hopfile found, run this command in a linked directory to use `{{deployment}}`"
+            );
+        };
+
+        path = path.replace(
+            "{deployment}",
+            &state.get_hopfile_deployment(&hopfile).await?.id,
+        );
+    }
+
+    Ok(path)
+}
+
+fn parse_field(field: &str) -> Result<(String, String)> {
+    let (key, value) = field
+        .split_once('=')
+        .with_context(|| format!("Invalid key=value: no `=` found in `{field}`"))?;
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn fields_to_body(fields: &[(String, String)]) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect()
+}
+
+async fn read_input(input: &PathBuf) -> Result<String> {
+    let mut body = String::new();
+
+    if input.as_os_str() == "-" {
+        tokio::io::stdin().read_to_string(&mut body).await?;
+    } else {
+        body = tokio::fs::read_to_string(input)
+            .await
+            .with_context(|| format!("Failed to read `{}`", input.display()))?;
+    }
+
+    ensure!(
+        serde_json::from_str::<Value>(&body).is_ok(),
+        "Input is not valid JSON"
+    );
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fields_to_body() {
+        let body = fields_to_body(&[
+            ("name".to_string(), "api".to_string()),
+            ("type".to_string(), "persistent".to_string()),
+        ]);
+
+        assert_eq!(
+            Value::Object(body),
+            serde_json::json!({ "name": "api", "type": "persistent" })
+        );
+    }
+}
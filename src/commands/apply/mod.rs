@@ -0,0 +1,124 @@
+pub mod utils;
+
+use std::env::current_dir;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use self::utils::{execute, plan, ExecuteOptions};
+use crate::commands::projects::utils::format_project;
+use crate::config::EXEC_NAME;
+use crate::errors::HopError;
+use crate::state::State;
+use crate::store::hopfile::{HopFile, LATEST_VERSION};
+use crate::utils::{logging, prompt};
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Create or update a deployment to match the hopfile",
+    long_about = "Create or update a deployment to match the hopfile\n\nThe `deployment` section of a version 2 hopfile describes the deployment with its Gateways and health checks. Fields that are left out keep their current value."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        name = "dir",
+        help = "Directory with the hopfile, defaults to current directory"
+    )]
+    pub path: Option<PathBuf>,
+
+    #[clap(short, long, help = "Skip confirmation")]
+    pub yes: bool,
+
+    #[clap(
+        long,
+        help = "Delete Gateways and health checks that are not in the hopfile"
+    )]
+    pub prune: bool,
+
+    #[clap(long, help = "Do not roll out the changes")]
+    pub no_rollout: bool,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    let mut dir = current_dir()?;
+
+    if let Some(path) = options.path {
+        dir = dir.join(path).canonicalize()?;
+    }
+
+    let hopfile = HopFile::find(dir.clone())
+        .await?
+        .with_context(|| format!("No hopfile found in {}", dir.display()))?;
+
+    let Some(spec) = &hopfile.deployment else {
+        bail!(
+            "{} does not describe a deployment, add a `deployment` section and set `version: {LATEST_VERSION}` to use `{EXEC_NAME} apply`",
+            hopfile.path.display()
+        );
+    };
+
+    let project = state.get_hopfile_project(&hopfile)?;
+    logging::set_context("project", &project.id);
+
+    log::info!("Applying {}", hopfile.path.display());
+    log::info!("Project: {}", format_project(&project));
+
+    let deployment = state.find_hopfile_deployment(&hopfile).await?;
+
+    if let Some(deployment) = &deployment {
+        logging::set_context("deployment", &deployment.id);
+    }
+
+    let mut plan = plan(&state, &project, spec, deployment.as_ref()).await?;
+
+    if !options.prune {
+        let kept = plan.remove_deletes();
+
+        if kept > 0 {
+            log::warn!(
+                "{kept} Gateways or health checks are not in the hopfile, they are only deleted with `--prune`"
+            );
+        }
+    }
+
+    if plan.is_empty() {
+        log::info!("Everything is up to date");
+
+        return Ok(());
+    }
+
+    println!("{}", plan.format()?.join("\n"));
+
+    if !options.yes
+        && !prompt::Confirm::new("--yes")
+            .with_prompt("Apply these changes?")
+            .interact_opt()?
+            .unwrap_or(false)
+    {
+        bail!(HopError::cancelled());
+    }
+
+    let needs_build = plan.needs_build;
+
+    let deployment = execute(
+        &state,
+        &project,
+        deployment,
+        plan,
+        ExecuteOptions {
+            prune: options.prune,
+            no_rollout: options.no_rollout,
+        },
+    )
+    .await?;
+
+    if needs_build {
+        log::info!(
+            "Run `{EXEC_NAME} deploy` to build the image and start the containers of `{}`",
+            deployment.name
+        );
+    }
+
+    Ok(())
+}
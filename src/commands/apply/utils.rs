@@ -0,0 +1,760 @@
+use anyhow::Result;
+use console::style;
+use serde::Serialize;
+
+use crate::commands::auth::docker::HOP_REGISTRY_URL;
+use crate::commands::domains::util::attach_domain;
+use crate::commands::gateways::types::{Gateway, GatewayConfig, GatewayType};
+use crate::commands::gateways::util::{
+    create_gateway, delete_gateway, get_all_gateways, update_gateway,
+};
+use crate::commands::ignite::health::types::{CreateHealthCheck, HealthCheck};
+use crate::commands::ignite::health::utils::{
+    create_health_check, delete_health_check, get_all_health_checks,
+};
+use crate::commands::ignite::types::{CreateDeployment, Deployment, Image};
+use crate::commands::ignite::utils::{create_deployment, rollout, scale, update_deployment};
+use crate::commands::projects::types::Project;
+use crate::commands::secrets::utils::{get_secret_name, get_secrets};
+use crate::state::State;
+use crate::store::hopfile::{DeploymentSpec, GatewaySpec};
+use crate::utils::diff;
+
+/// A change to a single resource needed to match the hopfile
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<T> {
+    Create(T),
+    Update {
+        id: String,
+        current: T,
+        desired: T,
+    },
+    /// Resources that cannot be updated in place are deleted and created again
+    Replace {
+        id: String,
+        current: T,
+        desired: T,
+    },
+    Delete {
+        id: String,
+        current: T,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub deployment: Option<Change<CreateDeployment>>,
+    /// Current and desired amount of containers
+    pub containers: Option<(u64, u64)>,
+    pub gateways: Vec<Change<GatewaySpec>>,
+    pub health_checks: Vec<Change<CreateHealthCheck>>,
+    /// The image is built by `hop deploy` so containers can only be created after that
+    pub needs_build: bool,
+    /// Secrets used in env that do not exist in the project
+    pub missing_secrets: Vec<String>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.deployment.is_none()
+            && self.containers.is_none()
+            && self.gateways.is_empty()
+            && self.health_checks.is_empty()
+    }
+
+    /// Drops the deletes that only happen with `--prune`, returns how many there were
+    pub fn remove_deletes(&mut self) -> usize {
+        let before = self.gateways.len() + self.health_checks.len();
+
+        self.gateways
+            .retain(|change| !matches!(change, Change::Delete { .. }));
+        self.health_checks
+            .retain(|change| !matches!(change, Change::Delete { .. }));
+
+        before - self.gateways.len() - self.health_checks.len()
+    }
+
+    /// Every change with the fields it changes, e.g.
+    /// `~ deployment api` followed by `  ~ resources.ram: "256M" -> "1G"`
+    pub fn format(&self) -> Result<Vec<String>> {
+        let mut lines = vec![];
+
+        if let Some(change) = &self.deployment {
+            format_change(&mut lines, change, "deployment", |config| {
+                config.name.clone().unwrap_or_default()
+            })?;
+        }
+
+        if let Some((current, desired)) = self.containers {
+            lines.push(
+                style(format!("~ containers: {current} -> {desired}"))
+                    .yellow()
+                    .to_string(),
+            );
+        }
+
+        for change in &self.gateways {
+            format_change(&mut lines, change, "Gateway", gateway_label)?;
+        }
+
+        for change in &self.health_checks {
+            format_change(&mut lines, change, "health check", |check| {
+                format!("{} {}:{}", check.protocol, check.port, check.path)
+            })?;
+        }
+
+        for secret in &self.missing_secrets {
+            lines.push(
+                style(format!(
+                    "! secret {secret} is used in env but does not exist"
+                ))
+                .red()
+                .bold()
+                .to_string(),
+            );
+        }
+
+        Ok(lines)
+    }
+}
+
+fn format_change<T: Serialize>(
+    lines: &mut Vec<String>,
+    change: &Change<T>,
+    kind: &str,
+    label: impl Fn(&T) -> String,
+) -> Result<()> {
+    let (header, fields) = match change {
+        Change::Create(desired) => (
+            style(format!("+ {kind} {}", label(desired))).green(),
+            diff::additions(desired)?,
+        ),
+
+        Change::Update {
+            id,
+            current,
+            desired,
+        } => (
+            style(format!("~ {kind} {} ({id})", label(desired))).yellow(),
+            diff::diff(current, desired)?,
+        ),
+
+        Change::Replace {
+            id,
+            current,
+            desired,
+        } => (
+            style(format!("-/+ {kind} {} ({id})", label(desired))).yellow(),
+            diff::diff(current, desired)?,
+        ),
+
+        Change::Delete { id, current } => (
+            style(format!("- {kind} {} ({id})", label(current))).red(),
+            vec![],
+        ),
+    };
+
+    lines.push(header.bold().to_string());
+    lines.extend(fields.iter().map(|field| format!("    {}", field.format())));
+
+    Ok(())
+}
+
+fn gateway_label(gateway: &GatewaySpec) -> String {
+    let config = &gateway.config;
+
+    match (&config.name, config.target_port) {
+        (Some(name), _) => name.clone(),
+        (None, Some(port)) => format!("on port {port}"),
+        (None, None) => config.type_.clone().unwrap_or_default().to_string(),
+    }
+}
+
+/// Compares the spec with the deployment and its resources,
+/// fields left out of the spec keep their current value
+pub async fn plan(
+    state: &State,
+    project: &Project,
+    spec: &DeploymentSpec,
+    deployment: Option<&Deployment>,
+) -> Result<Plan> {
+    let Some(deployment) = deployment else {
+        let mut desired = spec.config.clone();
+        let needs_build = desired.image.is_none();
+
+        // same image `hop deploy` pushes to
+        desired.image = desired.image.or_else(|| {
+            Some(Image {
+                name: format!(
+                    "{HOP_REGISTRY_URL}/{}/{}",
+                    project.namespace,
+                    desired.name.clone().unwrap_or_default()
+                ),
+            })
+        });
+
+        return Ok(Plan {
+            deployment: Some(Change::Create(desired)),
+            containers: spec
+                .containers
+                .filter(|_| !needs_build)
+                .map(|count| (0, count)),
+            gateways: spec
+                .gateways
+                .iter()
+                .map(|config| Change::Create(with_gateway_defaults(config)))
+                .collect(),
+            health_checks: spec
+                .health_checks
+                .iter()
+                .cloned()
+                .map(Change::Create)
+                .collect(),
+            needs_build,
+            missing_secrets: missing_secrets(state, project, &spec.config).await?,
+        });
+    };
+
+    let (gateways, health_checks) = tokio::join!(
+        get_all_gateways(&state.http, &deployment.id),
+        get_all_health_checks(&state.http, &deployment.id)
+    );
+
+    let (deployment_change, containers) = plan_existing_deployment(spec, deployment);
+
+    Ok(Plan {
+        deployment: deployment_change,
+        containers,
+        gateways: plan_gateways(&spec.gateways, &gateways?),
+        health_checks: plan_health_checks(&spec.health_checks, &health_checks?),
+        needs_build: false,
+        missing_secrets: missing_secrets(state, project, &spec.config).await?,
+    })
+}
+
+/// Plan of changing only the deployment, as `hop ignite update` does
+pub async fn plan_deployment_update(
+    state: &State,
+    project: &Project,
+    deployment: &Deployment,
+    desired: CreateDeployment,
+    containers: Option<u64>,
+) -> Result<Plan> {
+    let current = CreateDeployment::from(deployment.clone());
+
+    Ok(Plan {
+        missing_secrets: missing_secrets(state, project, &desired).await?,
+        deployment: (desired != current).then(|| Change::Update {
+            id: deployment.id.clone(),
+            current,
+            desired,
+        }),
+        containers: containers
+            .filter(|count| *count != deployment.target_container_count)
+            .map(|count| (deployment.target_container_count, count)),
+        gateways: vec![],
+        health_checks: vec![],
+        needs_build: false,
+    })
+}
+
+/// Names of the secrets used in env as `${secrets.NAME}` that do not exist
+async fn missing_secrets(
+    state: &State,
+    project: &Project,
+    config: &CreateDeployment,
+) -> Result<Vec<String>> {
+    let mut used = config
+        .env
+        .values()
+        .filter_map(|value| get_secret_name(value))
+        .map(|name| name.to_uppercase())
+        .collect::<Vec<_>>();
+
+    if used.is_empty() {
+        return Ok(used);
+    }
+
+    let secrets = get_secrets(&state.http, &project.id).await?;
+
+    used.sort();
+    used.dedup();
+    used.retain(|name| !secrets.iter().any(|secret| secret.name == *name));
+
+    Ok(used)
+}
+
+/// Changes of the deployment itself and of its amount of containers
+fn plan_existing_deployment(
+    spec: &DeploymentSpec,
+    deployment: &Deployment,
+) -> (Option<Change<CreateDeployment>>, Option<(u64, u64)>) {
+    let current = CreateDeployment::from(deployment.clone());
+    let desired = merge_deployment(spec, &current);
+
+    let containers = spec
+        .containers
+        .filter(|count| *count != deployment.target_container_count)
+        .map(|count| (deployment.target_container_count, count));
+
+    let change = (desired != current).then(|| Change::Update {
+        id: deployment.id.clone(),
+        current,
+        desired,
+    });
+
+    (change, containers)
+}
+
+fn merge_deployment(spec: &DeploymentSpec, current: &CreateDeployment) -> CreateDeployment {
+    let mut desired = spec.config.clone();
+
+    desired.name = desired.name.or_else(|| current.name.clone());
+    desired.image = desired.image.or_else(|| current.image.clone());
+    desired.type_ = desired.type_.or_else(|| current.type_.clone());
+    desired.volume = desired.volume.or_else(|| current.volume.clone());
+    desired.entrypoint = desired.entrypoint.or_else(|| current.entrypoint.clone());
+    desired.command = desired.command.or_else(|| current.command.clone());
+    desired.restart_policy = desired
+        .restart_policy
+        .or_else(|| current.restart_policy.clone());
+
+    // these have defaults, so only the hopfile knows whether they were left out
+    if !spec.has("env") {
+        desired.env = current.env.clone();
+    }
+
+    if !spec.has("resources") {
+        desired.resources = current.resources.clone();
+    }
+
+    if !spec.has("container_strategy") {
+        desired.container_strategy = current.container_strategy.clone();
+    }
+
+    desired
+}
+
+fn plan_gateways(spec: &[GatewaySpec], gateways: &[Gateway]) -> Vec<Change<GatewaySpec>> {
+    let mut remaining = gateways.iter().collect::<Vec<_>>();
+    let mut changes = vec![];
+
+    for desired in spec {
+        let Some(idx) = remaining
+            .iter()
+            .position(|gateway| gateway_matches(&desired.config, gateway))
+        else {
+            changes.push(Change::Create(with_gateway_defaults(desired)));
+            continue;
+        };
+
+        let gateway = remaining.remove(idx);
+        let current = GatewaySpec::from_gateway(gateway);
+        let (config, current_config) = (&desired.config, &current.config);
+
+        // domains are only attached, they are removed with `hop domains rm`
+        let mut domains = current.domains.clone();
+        domains.extend(desired.domains.iter().cloned());
+        domains.sort();
+        domains.dedup();
+
+        let merged = GatewaySpec {
+            config: GatewayConfig {
+                type_: current_config.type_.clone(),
+                protocol: config
+                    .protocol
+                    .clone()
+                    .or_else(|| current_config.protocol.clone()),
+                name: config.name.clone().or_else(|| current_config.name.clone()),
+                target_port: config.target_port.or(current_config.target_port),
+                internal_domain: config
+                    .internal_domain
+                    .clone()
+                    .or_else(|| current_config.internal_domain.clone()),
+            },
+            domains,
+        };
+
+        if merged != current {
+            changes.push(Change::Update {
+                id: gateway.id.clone(),
+                current,
+                desired: merged,
+            });
+        }
+    }
+
+    changes.extend(remaining.into_iter().map(|gateway| Change::Delete {
+        id: gateway.id.clone(),
+        current: GatewaySpec::from_gateway(gateway),
+    }));
+
+    changes
+}
+
+/// Values the API requires when creating a Gateway
+fn with_gateway_defaults(gateway: &GatewaySpec) -> GatewaySpec {
+    let config = &gateway.config;
+    let type_ = config.type_.clone().unwrap_or_default();
+
+    GatewaySpec {
+        config: GatewayConfig {
+            protocol: match type_ {
+                GatewayType::External => {
+                    config.protocol.clone().or_else(|| Some(Default::default()))
+                }
+                GatewayType::Internal => config.protocol.clone(),
+            },
+            type_: Some(type_),
+            ..config.clone()
+        },
+        domains: gateway.domains.clone(),
+    }
+}
+
+/// Gateways are matched by name, or by type and port when the spec does not name them,
+/// the type of a Gateway cannot be changed so a different type is a different Gateway
+fn gateway_matches(desired: &GatewayConfig, gateway: &Gateway) -> bool {
+    if desired.type_.clone().unwrap_or_default() != gateway.type_ {
+        return false;
+    }
+
+    match &desired.name {
+        Some(name) => gateway.name.as_ref() == Some(name),
+
+        None => {
+            gateway.type_ == GatewayType::Internal
+                && desired.internal_domain.is_some()
+                && desired.internal_domain == gateway.internal_domain
+                || desired.target_port.is_some() && desired.target_port == gateway.target_port
+        }
+    }
+}
+
+fn plan_health_checks(
+    spec: &[CreateHealthCheck],
+    checks: &[HealthCheck],
+) -> Vec<Change<CreateHealthCheck>> {
+    let mut remaining = checks.iter().collect::<Vec<_>>();
+    let mut changes = vec![];
+
+    for desired in spec {
+        // health checks are identified by what they check
+        let Some(idx) = remaining.iter().position(|check| {
+            check.path == desired.path
+                && check.port == u64::from(desired.port)
+                && check.protocol.eq_ignore_ascii_case(&desired.protocol)
+        }) else {
+            changes.push(Change::Create(desired.clone()));
+            continue;
+        };
+
+        let check = remaining.remove(idx);
+        let current = CreateHealthCheck::from(check);
+
+        // there is no way to update a health check
+        if current != *desired {
+            changes.push(Change::Replace {
+                id: check.id.clone(),
+                current,
+                desired: desired.clone(),
+            });
+        }
+    }
+
+    changes.extend(remaining.into_iter().map(|check| Change::Delete {
+        id: check.id.clone(),
+        current: CreateHealthCheck::from(check),
+    }));
+
+    changes
+}
+
+async fn attach_domains(
+    state: &State,
+    gateway_id: &str,
+    domains: &[String],
+    attached: &[String],
+) -> Result<()> {
+    for domain in domains.iter().filter(|domain| !attached.contains(domain)) {
+        attach_domain(&state.http, gateway_id, domain).await?;
+
+        log::info!("Attached `{domain}` to Gateway `{gateway_id}`");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecuteOptions {
+    /// Delete Gateways and health checks that are not in the hopfile
+    pub prune: bool,
+    pub no_rollout: bool,
+}
+
+/// Makes the changes of the plan, resources that are not in the
+/// hopfile are only deleted with [`ExecuteOptions::prune`]
+pub async fn execute(
+    state: &State,
+    project: &Project,
+    deployment: Option<Deployment>,
+    plan: Plan,
+    options: ExecuteOptions,
+) -> Result<Deployment> {
+    let (mut deployment, updated) = match (plan.deployment, deployment) {
+        (Some(Change::Create(config)), _) => {
+            let deployment = create_deployment(&state.http, &project.id, &config).await?;
+
+            log::info!(
+                "Created deployment `{}` ({})",
+                deployment.name,
+                deployment.id
+            );
+
+            (deployment, false)
+        }
+
+        (Some(Change::Update { id, desired, .. } | Change::Replace { id, desired, .. }), _) => {
+            let deployment = update_deployment(&state.http, &id, &desired).await?;
+
+            log::info!(
+                "Updated deployment `{}` ({})",
+                deployment.name,
+                deployment.id
+            );
+
+            (deployment, true)
+        }
+
+        (None, Some(deployment)) => (deployment, false),
+
+        (Some(Change::Delete { .. }), _) | (None, None) => {
+            unreachable!("deployments are never deleted")
+        }
+    };
+
+    if let Some((_, count)) = plan.containers {
+        if deployment.can_scale() {
+            scale(&state.http, &deployment.id, count).await?;
+
+            log::info!("Scaled to {count} containers");
+
+            deployment.container_count = count;
+        } else {
+            log::warn!("The deployment can not be scaled manually, skipping the container count");
+        }
+    }
+
+    for change in plan.gateways {
+        match change {
+            Change::Create(desired) => {
+                let gateway = create_gateway(&state.http, &deployment.id, &desired.config).await?;
+
+                log::info!("Created Gateway `{}`", gateway.id);
+
+                attach_domains(state, &gateway.id, &desired.domains, &[]).await?;
+            }
+
+            Change::Update {
+                id,
+                current,
+                mut desired,
+            }
+            | Change::Replace {
+                id,
+                current,
+                mut desired,
+            } => {
+                if desired.config != current.config {
+                    // the type is only sent when creating a Gateway
+                    desired.config.type_ = None;
+
+                    update_gateway(&state.http, &id, &desired.config).await?;
+
+                    log::info!("Updated Gateway `{id}`");
+                }
+
+                attach_domains(state, &id, &desired.domains, &current.domains).await?;
+            }
+
+            Change::Delete { id, .. } if options.prune => {
+                delete_gateway(&state.http, &id).await?;
+
+                log::info!("Deleted Gateway `{id}`");
+            }
+
+            // left alone, `hop apply` warns about them before asking for confirmation
+            Change::Delete { .. } => {}
+        }
+    }
+
+    for change in plan.health_checks {
+        match change {
+            Change::Create(config) => {
+                let check = create_health_check(&state.http, &deployment.id, config).await?;
+
+                log::info!("Created health check `{}`", check.id);
+            }
+
+            Change::Update { id, desired, .. } | Change::Replace { id, desired, .. } => {
+                // created first so a failure does not leave the deployment without it
+                let check = create_health_check(&state.http, &deployment.id, desired).await?;
+                delete_health_check(&state.http, &id).await?;
+
+                log::info!("Replaced health check `{id}` with `{}`", check.id);
+            }
+
+            Change::Delete { id, .. } if options.prune => {
+                delete_health_check(&state.http, &id).await?;
+
+                log::info!("Deleted health check `{id}`");
+            }
+
+            // left alone, `hop apply` warns about them before asking for confirmation
+            Change::Delete { .. } => {}
+        }
+    }
+
+    if updated && deployment.can_rollout() && !options.no_rollout {
+        log::info!("Rolling out new containers");
+
+        rollout(&state.http, &deployment.id).await?;
+    }
+
+    Ok(deployment)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::commands::gateways::types::GatewayProtocol;
+    use crate::commands::ignite::types::{Config, Resources, RestartPolicy, ScalingStrategy};
+    use crate::store::hopfile::HopFile;
+
+    fn gateway(id: &str, name: Option<&str>, port: u16) -> Gateway {
+        Gateway {
+            id: id.to_string(),
+            name: name.map(String::from),
+            protocol: Some(GatewayProtocol::Http),
+            target_port: Some(port),
+            ..Default::default()
+        }
+    }
+
+    /// Deployment of a hopfile, which knows the fields that are written
+    fn parse_spec(deployment: &str) -> DeploymentSpec {
+        HopFile::deserialize_environment(
+            PathBuf::from("hop.yml"),
+            &format!("version: 2\ndeployment:\n  name: api\n  {deployment}\n"),
+            &HashMap::new(),
+            None,
+        )
+        .unwrap()
+        .deployment
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_keeps_omitted_fields() {
+        let deployment = Deployment {
+            id: "deployment_1".to_string(),
+            name: "api".to_string(),
+            target_container_count: 2,
+            config: Config {
+                env: HashMap::from([("TOKEN".to_string(), "secret".to_string())]),
+                container_strategy: ScalingStrategy::Autoscaled,
+                resources: Resources {
+                    vcpu: 2.0,
+                    ram: "4GB".to_string(),
+                    vgpu: vec![],
+                },
+                restart_policy: Some(RestartPolicy::Never),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let spec = parse_spec("restart_policy: always");
+        let (change, containers) = plan_existing_deployment(&spec, &deployment);
+
+        let Some(Change::Update {
+            current, desired, ..
+        }) = change
+        else {
+            panic!("expected an update, got {change:?}");
+        };
+
+        assert_eq!(desired.restart_policy, Some(RestartPolicy::Always));
+        assert_eq!(desired.env, current.env);
+        assert_eq!(desired.resources, current.resources);
+        assert_eq!(desired.container_strategy, ScalingStrategy::Autoscaled);
+        assert_eq!(containers, None);
+
+        // fields that are written replace the current value
+        let spec = parse_spec("env: {}\n  containers: 3");
+        let (change, containers) = plan_existing_deployment(&spec, &deployment);
+
+        assert!(matches!(change, Some(Change::Update { desired, .. }) if desired.env.is_empty()));
+        assert_eq!(containers, Some((2, 3)));
+
+        let spec = parse_spec("restart_policy: never");
+
+        assert_eq!(plan_existing_deployment(&spec, &deployment), (None, None));
+    }
+
+    #[test]
+    fn test_plan_gateways() {
+        let spec = vec![
+            GatewaySpec {
+                config: GatewayConfig {
+                    name: Some("web".to_string()),
+                    target_port: Some(3000),
+                    ..Default::default()
+                },
+                domains: vec!["example.com".to_string()],
+            },
+            GatewaySpec {
+                config: GatewayConfig {
+                    target_port: Some(9000),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        let gateways = vec![
+            gateway("gateway_1", Some("web"), 8080),
+            gateway("gateway_2", None, 5000),
+        ];
+
+        let changes = plan_gateways(&spec, &gateways);
+
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(
+            &changes[0],
+            Change::Update { id, desired, .. } if id == "gateway_1"
+                && desired.config.target_port == Some(3000)
+                && desired.domains == ["example.com"]
+        ));
+        assert!(matches!(
+            &changes[1],
+            Change::Create(desired) if desired.config.target_port == Some(9000)
+        ));
+        assert!(matches!(&changes[2], Change::Delete { id, .. } if id == "gateway_2"));
+
+        // without `--prune` a plan of only deletes has nothing to apply
+        let mut plan = Plan {
+            deployment: None,
+            containers: None,
+            gateways: vec![changes[2].clone()],
+            health_checks: vec![],
+            needs_build: false,
+            missing_secrets: vec![],
+        };
+
+        assert_eq!(plan.remove_deletes(), 1);
+        assert!(plan.is_empty());
+    }
+}
@@ -17,7 +17,9 @@ pub fn handle(options: &Options, state: &State) -> Result<()> {
 
     ensure!(!users.is_empty(), "There are no authorized users");
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&users)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = users
             .iter()
             .map(|d| d.as_str())
@@ -7,7 +7,7 @@ use tokio::sync::mpsc::Sender;
 use super::WEB_AUTH_URL;
 use crate::commands::auth::login::PAT_FALLBACK_URL;
 use crate::utils::browser::listen_for_callback;
-use crate::utils::parse_key_val;
+use crate::utils::{parse_key_val, prompt};
 
 pub async fn browser_login() -> Result<String> {
     let port = portpicker::pick_unused_port().with_context(|| {
@@ -27,7 +27,7 @@ pub async fn browser_login() -> Result<String> {
         log::info!("You can create one at {PAT_FALLBACK_URL}");
 
         // fallback to simple input
-        dialoguer::Password::new()
+        prompt::Password::new("--token")
             .with_prompt("Enter your token")
             .interact()
             .map_err(|why| anyhow!(why))
@@ -4,6 +4,7 @@ use super::types::{KeyType, LoginRequest, LoginResponse, SecondFactorRequest};
 use super::Options;
 use crate::config::EXEC_NAME;
 use crate::state::http::HttpClient;
+use crate::utils::prompt;
 
 pub async fn flags_login(options: Options, http: HttpClient) -> Result<String> {
     match options {
@@ -14,7 +15,7 @@ pub async fn flags_login(options: Options, http: HttpClient) -> Result<String> {
             ..
         } => {
             if options.token.is_none() || options.token.as_ref().unwrap().is_empty() {
-                dialoguer::Password::new()
+                prompt::Password::new("--token")
                     .with_prompt("Token")
                     .interact()
                     .context("Error getting token")
@@ -35,7 +36,7 @@ pub async fn flags_login(options: Options, http: HttpClient) -> Result<String> {
         } => {
             let password =
                 if options.password.is_none() || options.password.as_ref().unwrap().is_empty() {
-                    dialoguer::Password::new()
+                    prompt::Password::new("--password")
                         .with_prompt("Password")
                         .interact()?
                 } else {
@@ -87,7 +88,7 @@ async fn login_with_credentials(
             for key_type in types {
                 match key_type {
                     KeyType::Totp => {
-                        let code = dialoguer::Input::new()
+                        let code = prompt::Input::new("--token")
                             .with_prompt("Please enter your TOTP code")
                             .interact()
                             .context("Error getting second factor code")?;
@@ -10,7 +10,7 @@ use self::browser_auth::browser_login;
 use self::flags_auth::flags_login;
 use crate::state::State;
 use crate::store::Store;
-use crate::utils::in_path;
+use crate::utils::{in_path, prompt};
 
 const WEB_AUTH_URL: &str = "https://console.hop.io/auth/callback/cli";
 const PAT_FALLBACK_URL: &str = "https://console.hop.io/settings/pats";
@@ -73,9 +73,9 @@ pub async fn token(token: &str, mut state: State) -> Result<()> {
         .insert(authorized.id.clone(), token.to_string());
     state.auth.save().await?;
 
-    if !state.is_ci
+    if prompt::is_interactive()
         && in_path("docker").await
-        && dialoguer::Confirm::new()
+        && prompt::Confirm::new("hop auth docker")
             .with_prompt("Docker was detected, would you like to login to the Hop registry?")
             .default(false)
             .interact()?
@@ -4,7 +4,6 @@ use serde_json::Value;
 
 use crate::state::http::HttpClient;
 use crate::state::State;
-use crate::store::context::Context;
 use crate::store::Store;
 
 #[derive(Debug, Parser)]
@@ -27,8 +26,9 @@ pub async fn handle(_options: Options, mut state: State) -> Result<()> {
     )
     .await?;
 
-    // clear all state
-    state.ctx = Context::default();
+    // clear the user and project of the selected context, other contexts are kept
+    state.ctx.default_user = None;
+    state.ctx.default_project = None;
     state.ctx.save().await?;
 
     // remove the user from the store
@@ -0,0 +1,37 @@
+use anyhow::{ensure, Result};
+use clap::Parser;
+
+use crate::state::State;
+use crate::store::credentials::Backend;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Move stored tokens to a different credential backend")]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        help = "Backend to move the tokens to, one of `keyring`, `encrypted-file` or `plaintext`",
+        default_value = "keyring"
+    )]
+    pub backend: Backend,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    ensure!(
+        state.auth.backend != options.backend,
+        "Tokens are already stored in the `{}` backend",
+        options.backend
+    );
+
+    let store = options.backend.open().await?;
+
+    state.auth.migrate(options.backend, store.into()).await?;
+    state.auth.save().await?;
+
+    log::info!(
+        "Moved {} token(s) to the `{}` backend",
+        state.auth.authorized.len(),
+        options.backend
+    );
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ pub mod docker;
 mod list;
 pub mod login;
 mod logout;
+mod migrate;
 mod switch;
 pub mod types;
 mod utils;
@@ -20,6 +21,7 @@ pub enum Commands {
     Switch(switch::Options),
     #[clap(alias = "registry")]
     Docker(docker::Options),
+    Migrate(migrate::Options),
 }
 
 #[derive(Debug, Parser)]
@@ -31,11 +33,17 @@ pub struct Options {
 }
 
 pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    // every command but login reads the stored tokens
+    if !matches!(options.commands, Commands::Login(_)) {
+        state.unlock_auth().await?;
+    }
+
     match options.commands {
         Commands::Login(options) => login::handle(options, state).await,
         Commands::Logout(options) => logout::handle(options, state).await,
         Commands::Switch(options) => switch::handle(options, state).await,
         Commands::List(options) => list::handle(&options, &state),
         Commands::Docker(options) => docker::handle(&options, &mut state).await,
+        Commands::Migrate(options) => migrate::handle(options, state).await,
     }
 }
@@ -4,13 +4,17 @@ use clap::Parser;
 use super::utils::format_users;
 use crate::config::EXEC_NAME;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Switch to a different user")]
 #[group(skip)]
-pub struct Options {}
+pub struct Options {
+    #[clap(help = "ID of the user to switch to")]
+    user: Option<String>,
+}
 
-pub async fn handle(_options: Options, state: State) -> Result<()> {
+pub async fn handle(options: Options, state: State) -> Result<()> {
     let users = state.auth.authorized.keys().collect::<Vec<_>>();
 
     ensure!(
@@ -19,15 +23,25 @@ pub async fn handle(_options: Options, state: State) -> Result<()> {
         EXEC_NAME
     );
 
-    let users_fmt = format_users(&users, false);
+    let user_id = if let Some(user) = options.user {
+        ensure!(
+            state.auth.authorized.contains_key(&user),
+            "You are not logged in as `{user}`, run `{} auth login` to login",
+            EXEC_NAME
+        );
+
+        user
+    } else {
+        let users_fmt = format_users(&users, false);
 
-    let idx = dialoguer::Select::new()
-        .with_prompt("Select a user")
-        .items(&users_fmt)
-        .default(0)
-        .interact()?;
+        let idx = prompt::Select::new("<USER>")
+            .with_prompt("Select a user")
+            .items(&users_fmt)
+            .default(0)
+            .interact()?;
 
-    let user_id = users.get(idx).unwrap().to_owned();
+        users[idx].to_owned()
+    };
 
-    super::login::token(state.auth.authorized.clone().get(user_id).unwrap(), state).await
+    super::login::token(state.auth.authorized.clone().get(&user_id).unwrap(), state).await
 }
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::commands::projects::types::Project;
 
@@ -11,10 +11,11 @@ pub struct User {
     pub username: String,
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct AuthorizedClient {
     pub id: String,
     pub name: String,
+    #[serde(skip_serializing)]
     pub leap_token: String,
     pub projects: Vec<Project>,
     pub email: String,
@@ -5,7 +5,7 @@ use serde_json::Value;
 use super::types::ChannelType;
 use crate::commands::channels::utils::create_channel;
 use crate::state::State;
-use crate::utils::validate_json_non_null;
+use crate::utils::{prompt, validate_json_non_null};
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 #[clap(about = "Create a new Channel")]
@@ -25,7 +25,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
     let (type_, id, init_state) = if Options::default() == options {
-        let type_ = dialoguer::Select::new()
+        let type_ = prompt::Select::new("--type")
             .with_prompt("Select a channel type")
             .items(&ChannelType::variants())
             .default(0)
@@ -33,13 +33,13 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
         let type_ = ChannelType::variants()[type_].clone();
 
-        let id = if dialoguer::Confirm::new()
+        let id = if prompt::Confirm::new("--id")
             .with_prompt("Do you want to specify a custom Channel ID?")
             .default(false)
             .interact()?
         {
             Some(
-                dialoguer::Input::<String>::new()
+                prompt::Input::<String>::new("--id")
                     .with_prompt("Enter a custom ID")
                     .interact()?,
             )
@@ -47,7 +47,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             None
         };
 
-        let state = dialoguer::Input::new()
+        let state = prompt::Input::new("--state")
             .with_prompt("Enter the initial state of the channel")
             .default("{}".to_string())
             .validate_with(|s: &String| -> Result<(), String> {
@@ -3,13 +3,15 @@ use clap::Parser;
 
 use super::utils::delete_channel;
 use crate::commands::channels::utils::{format_channels, get_all_channels};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete Channels")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "IDs of the Channels")]
+    #[clap(help = "IDs or ID prefixes of the Channels")]
     channels: Vec<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -20,13 +22,19 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
     let channels = if !options.channels.is_empty() {
-        options.channels
+        let mut ids = vec![];
+
+        for reference in &options.channels {
+            ids.push(state.get_channel_by_name_or_id(reference).await?.id);
+        }
+
+        ids
     } else {
         let channels = get_all_channels(&state.http, &project_id).await?;
         ensure!(!channels.is_empty(), "No Channels found");
         let channels_fmt = format_channels(&channels, false);
 
-        let idxs = dialoguer::MultiSelect::new()
+        let idxs = prompt::MultiSelect::new("<CHANNELS>")
             .with_prompt("Select a Channel")
             .items(&channels_fmt)
             .interact()?;
@@ -40,7 +48,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete {} Channels?",
                 channels.len()
@@ -49,7 +57,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     let mut delete_count = 0;
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use clap::Parser;
 
-use super::utils::{format_channels, get_all_channels};
+use super::utils::{format_channels, get_channels};
+use crate::state::http::pagination::PageOptions;
 use crate::state::State;
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
@@ -10,13 +11,18 @@ use crate::state::State;
 pub struct Options {
     #[clap(short, long, help = "Only print the IDs of the Channels")]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub page: PageOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
-    let channels = get_all_channels(&state.http, &project_id).await?;
+    let channels = get_channels(&state.http, &project_id, options.page).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&channels)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = channels
             .iter()
             .map(|d| d.id.as_str())
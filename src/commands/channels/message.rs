@@ -5,12 +5,17 @@ use super::types::EventOptions;
 use super::utils::{format_channels, get_all_channels, message_channel};
 use crate::commands::channels::utils::get_json_input;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 #[clap(about = "Send a message to a Channel")]
 #[group(skip)]
 pub struct Options {
-    #[clap(short, long, help = "The ID of the Channel to send the message to")]
+    #[clap(
+        short,
+        long,
+        help = "ID or ID prefix of the Channel to send the message to"
+    )]
     channel: Option<String>,
 
     #[clap(flatten)]
@@ -21,7 +26,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
     let channel_id = if let Some(channel) = options.channel {
-        channel
+        state.get_channel_by_name_or_id(&channel).await?.id
     } else {
         let channels = get_all_channels(&state.http, &project_id).await?;
         ensure!(
@@ -30,7 +35,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         );
         let channels_fmt = format_channels(&channels, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--channel")
             .with_prompt("Select a Channel")
             .items(&channels_fmt)
             .default(0)
@@ -50,16 +55,16 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 .map(|d| serde_json::from_str(&d).unwrap()),
         )
     } else {
-        let event_name = dialoguer::Input::<String>::new()
+        let event_name = prompt::Input::<String>::new("--event")
             .with_prompt("Enter the event to send to the Channel")
             .interact_text()?;
 
-        let event_data = if dialoguer::Confirm::new()
+        let event_data = if prompt::Confirm::new("--data")
             .with_prompt("Do you want to specify event data?")
             .default(false)
             .interact()?
         {
-            Some(get_json_input()?)
+            Some(get_json_input(&state.config.editor())?)
         } else {
             None
         };
@@ -3,9 +3,9 @@ mod delete;
 mod list;
 mod message;
 mod subscribe;
-mod tokens;
-mod types;
-mod utils;
+pub mod tokens;
+pub mod types;
+pub mod utils;
 
 use anyhow::Result;
 use clap::Parser;
@@ -4,17 +4,18 @@ use clap::Parser;
 use super::utils::{format_channels, get_all_channels, subscribe_to_channel};
 use crate::commands::channels::tokens::utils::{format_tokens, get_all_tokens};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 #[clap(about = "Subscribe a Leap Token to a Channel")]
 #[group(skip)]
 pub struct Options {
-    #[clap(short, long, help = "The ID of the Channel to subscribe to")]
+    #[clap(short, long, help = "ID or ID prefix of the Channel to subscribe to")]
     channel: Option<String>,
     #[clap(
         short,
         long,
-        help = "The ID of the Leap Token to subscribe to the Channel"
+        help = "ID or ID prefix of the Leap Token to subscribe to the Channel"
     )]
     token: Option<String>,
 }
@@ -22,8 +23,8 @@ pub struct Options {
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
-    let channel_id = if let Some(channel_id) = options.channel {
-        channel_id
+    let channel_id = if let Some(channel) = options.channel {
+        state.get_channel_by_name_or_id(&channel).await?.id
     } else {
         let channels = get_all_channels(&state.http, &project_id).await?;
         ensure!(
@@ -33,7 +34,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         );
         let channels_fmt = format_channels(&channels, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--channel")
             .with_prompt("Select a Channel")
             .items(&channels_fmt)
             .default(0)
@@ -42,8 +43,8 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         channels[idx].id.clone()
     };
 
-    let token_id = if let Some(token_id) = options.token {
-        token_id
+    let token_id = if let Some(token) = options.token {
+        state.get_token_by_name_or_id(&token).await?.id
     } else {
         let tokens = get_all_tokens(&state.http, &project_id).await?;
         ensure!(
@@ -53,7 +54,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         );
         let tokens_fmt = format_tokens(&tokens, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--token")
             .with_prompt("Select a Leap Token")
             .items(&tokens_fmt)
             .default(0)
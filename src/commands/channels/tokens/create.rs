@@ -5,7 +5,7 @@ use serde_json::Value;
 use super::utils::create_token;
 use crate::commands::channels::tokens::utils::parse_expiration;
 use crate::state::State;
-use crate::utils::validate_json;
+use crate::utils::{prompt, validate_json};
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 #[clap(about = "Create a new Leap Token")]
@@ -37,13 +37,13 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             options.expiration.map(|ex| parse_expiration(&ex).unwrap()),
         )
     } else {
-        let token_state = dialoguer::Input::<String>::new()
+        let token_state = prompt::Input::<String>::new("--state")
             .with_prompt("State")
             .default("null".to_string())
             .validate_with(|s: &String| validate_json(s).map(|_| ()))
             .interact_text()?;
 
-        let expires_at = dialoguer::Input::<String>::new()
+        let expires_at = prompt::Input::<String>::new("--expiration")
             .with_prompt("Expiration date")
             .default("0".to_string())
             .validate_with(|s: &String| parse_expiration(s).map(|_| ()))
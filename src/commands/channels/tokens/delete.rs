@@ -2,13 +2,15 @@ use anyhow::{bail, ensure, Result};
 use clap::Parser;
 
 use super::utils::{delete_token, format_tokens, get_all_tokens};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete Leap Tokens")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "IDs of the Leap Tokens")]
+    #[clap(help = "IDs or ID prefixes of the Leap Tokens")]
     tokens: Vec<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -19,13 +21,19 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
     let tokens = if !options.tokens.is_empty() {
-        options.tokens
+        let mut ids = vec![];
+
+        for reference in &options.tokens {
+            ids.push(state.get_token_by_name_or_id(reference).await?.id);
+        }
+
+        ids
     } else {
         let tokens = get_all_tokens(&state.http, &project_id).await?;
         ensure!(!tokens.is_empty(), "No Leap Tokens found");
         let tokens_fmt = format_tokens(&tokens, false);
 
-        let idxs = dialoguer::MultiSelect::new()
+        let idxs = prompt::MultiSelect::new("<TOKENS>")
             .with_prompt("Select a Leap Token")
             .items(&tokens_fmt)
             .interact()?;
@@ -39,7 +47,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete {} Leap Tokens?",
                 tokens.len()
@@ -48,7 +56,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     let mut delete_count = 0;
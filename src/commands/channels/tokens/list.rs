@@ -1,7 +1,8 @@
 use anyhow::Result;
 use clap::Parser;
 
-use super::utils::{format_tokens, get_all_tokens};
+use super::utils::{format_tokens, get_tokens};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
@@ -10,13 +11,18 @@ use crate::state::State;
 pub struct Options {
     #[clap(short, long, help = "Only print the IDs of the Tokens")]
     quiet: bool,
+
+    #[clap(flatten)]
+    limit: LimitOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
-    let tokens = get_all_tokens(&state.http, &project_id).await?;
+    let tokens = get_tokens(&state.http, &project_id, options.limit.into()).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&tokens)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = tokens
             .iter()
             .map(|d| d.id.as_str())
@@ -5,6 +5,7 @@ use super::utils::{format_tokens, get_all_tokens, message_token};
 use crate::commands::channels::types::EventOptions;
 use crate::commands::channels::utils::get_json_input;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 #[clap(about = "Send a message to a Leap Token")]
@@ -30,7 +31,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         );
         let channels_fmt = format_tokens(&tokens, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--token")
             .with_prompt("Select a Leap Token")
             .items(&channels_fmt)
             .default(0)
@@ -50,16 +51,16 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 .map(|d| serde_json::from_str(&d).unwrap()),
         )
     } else {
-        let event_name = dialoguer::Input::<String>::new()
+        let event_name = prompt::Input::<String>::new("--event")
             .with_prompt("Enter the event name to send")
             .interact_text()?;
 
-        let event_data = if dialoguer::Confirm::new()
+        let event_data = if prompt::Confirm::new("--data")
             .with_prompt("Do you want to specify event data?")
             .default(false)
             .interact()?
         {
-            Some(get_json_input()?)
+            Some(get_json_input(&state.config.editor())?)
         } else {
             None
         };
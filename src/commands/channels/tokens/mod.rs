@@ -2,8 +2,8 @@ mod create;
 mod delete;
 mod list;
 mod messages;
-mod types;
-pub(super) mod utils;
+pub mod types;
+pub mod utils;
 
 use anyhow::Result;
 use clap::Parser;
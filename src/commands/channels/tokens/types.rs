@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Deserialize)]
+use crate::impl_paginated;
+use crate::utils::resolve::Resolvable;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LeapToken {
     pub id: String,
     pub created_at: String,
@@ -9,6 +12,14 @@ pub struct LeapToken {
     pub expires_at: Option<String>,
 }
 
+impl Resolvable for LeapToken {
+    const KIND: &'static str = "Leap Token";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct CreateLeapToken {
     pub expires_at: Option<String>,
@@ -24,3 +35,5 @@ pub struct SingleLeapToken {
 pub struct MultipleLeapToken {
     pub tokens: Vec<LeapToken>,
 }
+
+impl_paginated!(MultipleLeapToken => tokens: LeapToken);
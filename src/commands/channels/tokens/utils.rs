@@ -9,6 +9,7 @@ use tabwriter::TabWriter;
 
 use super::types::{CreateLeapToken, LeapToken, MultipleLeapToken, SingleLeapToken};
 use crate::commands::channels::types::{EventData, MessageEvent};
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
 
 pub async fn create_token(
@@ -46,16 +47,20 @@ pub async fn delete_token(http: &HttpClient, project_id: &str, token: &str) -> R
 }
 
 pub async fn get_all_tokens(http: &HttpClient, project_id: &str) -> Result<Vec<LeapToken>> {
-    let response = http
-        .request::<MultipleLeapToken>(
-            "GET",
-            &format!("/channels/tokens?project={project_id}"),
-            None,
-        )
-        .await?
-        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+    get_tokens(http, project_id, PageOptions::default()).await
+}
 
-    Ok(response.tokens)
+pub async fn get_tokens(
+    http: &HttpClient,
+    project_id: &str,
+    page: PageOptions,
+) -> Result<Vec<LeapToken>> {
+    http.request_all::<MultipleLeapToken>(
+        &format!("/channels/tokens?project={project_id}"),
+        PageStyle::Unpaginated,
+        page,
+    )
+    .await
 }
 
 pub async fn message_token(
@@ -6,6 +6,8 @@ use clap::Parser;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::state::http::pagination::Paginated;
+use crate::utils::resolve::Resolvable;
 use crate::utils::validate_json;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -40,7 +42,7 @@ impl ChannelType {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Channel {
     pub id: String,
     #[serde(rename = "type")]
@@ -50,6 +52,14 @@ pub struct Channel {
     pub state: Value,
 }
 
+impl Resolvable for Channel {
+    const KIND: &'static str = "channel";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct CreateChannel {
     #[serde(rename = "type")]
@@ -69,6 +79,18 @@ pub struct PaginatedChannels {
     pub total_count: u64,
 }
 
+impl Paginated for PaginatedChannels {
+    type Item = Channel;
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.channels
+    }
+
+    fn total_count(&self) -> Option<u64> {
+        Some(self.total_count)
+    }
+}
+
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 pub struct EventOptions {
     #[clap(short = 'e', long = "event", help = "Event name to send")]
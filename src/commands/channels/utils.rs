@@ -7,7 +7,7 @@ use tabwriter::TabWriter;
 use super::types::{
     Channel, ChannelType, CreateChannel, EventData, MessageEvent, PaginatedChannels, SingleChannel,
 };
-use crate::config::DEFAULT_EDITOR;
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
 
 pub async fn create_channel(
@@ -41,47 +41,28 @@ pub async fn create_channel(
     Ok(response.channel)
 }
 
-const PAGE_SIZE: u64 = 75;
-
 pub async fn get_all_channels(http: &HttpClient, project_id: &str) -> Result<Vec<Channel>> {
-    let mut channels = vec![];
-
-    let mut page = 1;
-
-    loop {
-        let mut paginated = get_channels_in_page(http, project_id, page).await?;
-
-        // this might look annyoing but,
-        // to get the correct ordering of the channels
-        // we need to reverse the order of the pages
-        paginated.channels.extend(channels);
-        channels = paginated.channels;
-
-        if paginated.total_count <= channels.len().try_into()? {
-            break;
-        }
-
-        page += 1;
-    }
-
-    Ok(channels)
+    get_channels(http, project_id, PageOptions::default()).await
 }
 
-async fn get_channels_in_page(
+pub async fn get_channels(
     http: &HttpClient,
     project_id: &str,
-    page: usize,
-) -> Result<PaginatedChannels> {
-    let response = http
-        .request::<PaginatedChannels>(
-            "GET",
-            &format!("/channels?project={project_id}&page={page}&pageSize={PAGE_SIZE}"),
-            None,
+    page: PageOptions,
+) -> Result<Vec<Channel>> {
+    let mut channels = http
+        .request_all::<PaginatedChannels>(
+            &format!("/channels?project={project_id}"),
+            PageStyle::Page,
+            page,
         )
-        .await?
-        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+        .await?;
+
+    // the pages come oldest first, the whole list is reversed since
+    // the api can return smaller pages than the requested size
+    channels.reverse();
 
-    Ok(response)
+    Ok(channels)
 }
 
 pub async fn delete_channel(http: &HttpClient, project_id: &str, channel_id: &str) -> Result<()> {
@@ -158,14 +139,10 @@ pub fn format_channels(channels: &[Channel], title: bool) -> Vec<String> {
         .collect()
 }
 
-pub fn get_json_input() -> Result<EventData> {
-    let editor_cmd = std::env::var("EDITOR")
-        .or_else(|_| std::env::var("VISUAL"))
-        .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
-
+pub fn get_json_input(editor_cmd: &str) -> Result<EventData> {
     loop {
         match dialoguer::Editor::new()
-            .executable(&editor_cmd)
+            .executable(editor_cmd)
             .extension(".json")
             .require_save(true)
             .edit("")
@@ -0,0 +1,351 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::{Context, Result};
+use chrono::Duration;
+use clap::{ArgAction, Command, CommandFactory, Parser};
+
+use crate::commands::channels::utils::get_all_channels;
+use crate::commands::containers::utils::get_all_containers;
+use crate::commands::gateways::util::get_all_gateways;
+use crate::commands::ignite::utils::{get_all_deployments, get_premade};
+use crate::commands::secrets::utils::get_secrets;
+use crate::state::State;
+use crate::store::cache::CompletionCache;
+use crate::store::Store;
+use crate::CLI;
+
+/// Values younger than this are used without asking the API
+const CACHE_TTL_SECS: i64 = 5 * 60;
+/// Completions should not hang the shell, after this the stale cache is used
+const FETCH_TIMEOUT_SECS: u64 = 3;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Print completions for resource names, used by the completion scripts")]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        help = "Words of the command line after `hop`, the last one is the word being completed",
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    pub words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Deployments,
+    Containers,
+    Gateways,
+    Channels,
+    Projects,
+    Secrets,
+    Templates,
+}
+
+impl Resource {
+    /// Resource completed by an argument, `command` is the top level command
+    fn from_arg(command: &str, id: &str) -> Option<Self> {
+        match id {
+            "deployment" => Some(Self::Deployments),
+            "container" | "containers" => Some(Self::Containers),
+            "gateway" | "gateways" => Some(Self::Gateways),
+            "channel" | "channels" => Some(Self::Channels),
+            "project" => Some(Self::Projects),
+            "template" => Some(Self::Templates),
+            "name" if command == "secrets" => Some(Self::Secrets),
+            _ => None,
+        }
+    }
+
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Deployments => "deployments",
+            Self::Containers => "containers",
+            Self::Gateways => "gateways",
+            Self::Channels => "channels",
+            Self::Projects => "projects",
+            Self::Secrets => "secrets",
+            Self::Templates => "templates",
+        }
+    }
+
+    /// Projects and templates do not belong to a project
+    const fn is_project_scoped(self) -> bool {
+        !matches!(self, Self::Projects | Self::Templates)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Target {
+    pub resource: Resource,
+    /// `--project` passed on the command line being completed
+    pub project: Option<String>,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    let mut command = CLI::command();
+    command.build();
+
+    // the last word is the one being completed
+    let preceding = options
+        .words
+        .split_last()
+        .map(|(_, preceding)| preceding)
+        .unwrap_or_default();
+
+    let Some(target) = find_target(&command, preceding) else {
+        return Ok(());
+    };
+
+    if let Some(project) = &target.project {
+        state.ctx.project_override = Some(project.clone());
+    }
+
+    let key = if target.resource.is_project_scoped() {
+        format!(
+            "{}:{}",
+            target.resource.key(),
+            state.ctx.project_override.clone().unwrap_or_default()
+        )
+    } else {
+        target.resource.key().to_string()
+    };
+
+    let mut cache = CompletionCache::new().await?;
+
+    let values = if let Some(values) = cache.get_fresh(&key, Duration::seconds(CACHE_TTL_SECS)) {
+        values.to_vec()
+    } else {
+        let fetched = tokio::time::timeout(
+            StdDuration::from_secs(FETCH_TIMEOUT_SECS),
+            fetch(&mut state, target.resource),
+        )
+        .await
+        .context("Timed out")
+        .and_then(|values| values);
+
+        match fetched {
+            Ok(values) => {
+                cache.insert(key, values.clone());
+                cache.save().await?;
+
+                values
+            }
+
+            Err(error) => {
+                log::debug!("Failed to fetch completions, using the cache: {error}");
+
+                cache.get(&key).map(<[String]>::to_vec).unwrap_or_default()
+            }
+        }
+    };
+
+    for value in values {
+        println!("{value}");
+    }
+
+    Ok(())
+}
+
+/// Walks the command line to find the resource the next word should name
+pub fn find_target(root: &Command, words: &[String]) -> Option<Target> {
+    let mut command = root;
+    let mut top_level = "";
+    let mut positional = 0;
+    let mut project = None;
+    let mut pending = None;
+
+    for word in words {
+        // the word is the value of the previous flag
+        if let Some(id) = pending.take() {
+            if id == "project" {
+                project = Some(word.clone());
+            }
+
+            continue;
+        }
+
+        if let Some(flag) = word.strip_prefix("--") {
+            if flag.is_empty() {
+                continue;
+            }
+
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (flag, None),
+            };
+
+            let arg = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(name));
+
+            if let Some(arg) = arg.filter(|arg| takes_value(arg.get_action())) {
+                match value {
+                    Some(value) if arg.get_id() == "project" => project = Some(value.to_string()),
+                    Some(_) => {}
+                    None => pending = Some(arg.get_id().to_string()),
+                }
+            }
+        } else if let Some(flags) = word.strip_prefix('-').filter(|flags| !flags.is_empty()) {
+            // only the last flag of a group like `-fp` can take a value
+            let arg = flags.chars().last().and_then(|short| {
+                command
+                    .get_arguments()
+                    .find(|arg| arg.get_short() == Some(short))
+            });
+
+            if let Some(arg) = arg.filter(|arg| takes_value(arg.get_action())) {
+                pending = Some(arg.get_id().to_string());
+            }
+        } else if let Some(subcommand) = command.find_subcommand(word) {
+            if top_level.is_empty() {
+                top_level = subcommand.get_name();
+            }
+
+            command = subcommand;
+            positional = 0;
+        } else {
+            positional += 1;
+        }
+    }
+
+    let id = match pending {
+        Some(id) => id,
+
+        None => {
+            let positionals = command.get_positionals().collect::<Vec<_>>();
+
+            // arguments with multiple values take all the remaining words
+            let arg = positionals.get(positional).or_else(|| {
+                positionals
+                    .last()
+                    .filter(|arg| matches!(arg.get_action(), ArgAction::Append))
+            })?;
+
+            arg.get_id().to_string()
+        }
+    };
+
+    Resource::from_arg(top_level, &id).map(|resource| Target { resource, project })
+}
+
+const fn takes_value(action: &ArgAction) -> bool {
+    matches!(action, ArgAction::Set | ArgAction::Append)
+}
+
+async fn fetch(state: &mut State, resource: Resource) -> Result<Vec<String>> {
+    state.login(None).await?;
+
+    if resource == Resource::Projects {
+        return Ok(state
+            .ctx
+            .current
+            .as_ref()
+            .map(|me| me.projects.iter().map(|p| p.namespace.clone()).collect())
+            .unwrap_or_default());
+    }
+
+    if resource == Resource::Templates {
+        return Ok(get_premade(&state.http)
+            .await?
+            .into_iter()
+            .map(|premade| premade.name)
+            .collect());
+    }
+
+    let project_id = state.ctx.current_project_error()?.id;
+
+    let values = match resource {
+        Resource::Channels => get_all_channels(&state.http, &project_id)
+            .await?
+            .into_iter()
+            .map(|channel| channel.id)
+            .collect(),
+
+        Resource::Secrets => get_secrets(&state.http, &project_id)
+            .await?
+            .into_iter()
+            .map(|secret| secret.name)
+            .collect(),
+
+        _ => {
+            let deployments = get_all_deployments(&state.http, &project_id).await?;
+
+            let mut values = vec![];
+
+            for deployment in deployments {
+                match resource {
+                    Resource::Deployments => {
+                        values.push(deployment.name);
+                        values.push(deployment.id);
+                    }
+
+                    Resource::Containers => values.extend(
+                        get_all_containers(&state.http, &deployment.id)
+                            .await?
+                            .into_iter()
+                            .map(|container| container.id),
+                    ),
+
+                    Resource::Gateways => values.extend(
+                        get_all_gateways(&state.http, &deployment.id)
+                            .await?
+                            .into_iter()
+                            .map(|gateway| gateway.id),
+                    ),
+
+                    _ => unreachable!(),
+                }
+            }
+
+            values
+        }
+    };
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn target(line: &str) -> Option<Target> {
+        let mut command = CLI::command();
+        command.build();
+
+        let words = line
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        find_target(&command, &words)
+    }
+
+    #[test]
+    fn test_find_target() {
+        assert_eq!(
+            target("ignite scale"),
+            Some(Target {
+                resource: Resource::Deployments,
+                project: None
+            })
+        );
+        assert_eq!(
+            target("ignite --project api gateways rm gateway_1"),
+            Some(Target {
+                resource: Resource::Gateways,
+                project: Some("api".to_string())
+            })
+        );
+        assert_eq!(
+            target("deploy -p").map(|target| target.resource),
+            Some(Resource::Projects)
+        );
+        assert_eq!(
+            target("secrets rm").map(|target| target.resource),
+            Some(Resource::Secrets)
+        );
+        assert_eq!(target("ignite scale api"), None);
+        assert_eq!(target("projects new"), None);
+    }
+}
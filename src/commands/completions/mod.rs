@@ -1,4 +1,6 @@
-use std::io;
+pub mod dynamic;
+
+use std::io::{self, Write};
 
 use clap::{CommandFactory, Parser};
 use clap_complete::{generate, Shell as CompletionShell};
@@ -8,7 +10,10 @@ use crate::state::State;
 use crate::CLI;
 
 #[derive(Debug, Parser)]
-#[clap(about = "Generate completion scripts for the specified shell")]
+#[clap(
+    about = "Generate completion scripts for the specified shell",
+    long_about = "Generate completion scripts for the specified shell\n\nThe scripts for bash, zsh and fish also complete the names and IDs of deployments, containers, gateways, channels, projects, secrets and templates."
+)]
 #[group(skip)]
 pub struct Options {
     #[clap(help = "The shell to print the completion script for")]
@@ -16,10 +21,55 @@ pub struct Options {
 }
 
 pub fn handle(options: Options, _state: State) {
-    generate(
-        options.shell,
-        &mut CLI::command(),
-        EXEC_NAME,
-        &mut io::stdout().lock(),
-    )
+    let mut stdout = io::stdout().lock();
+
+    generate(options.shell, &mut CLI::command(), EXEC_NAME, &mut stdout);
+
+    if let Some(script) = dynamic_script(options.shell) {
+        writeln!(stdout, "\n{}", script.replace("{bin}", EXEC_NAME)).ok();
+    }
+}
+
+/// Wraps the generated completions to ask `hop complete-resources` for resource names first
+fn dynamic_script(shell: CompletionShell) -> Option<&'static str> {
+    match shell {
+        CompletionShell::Bash => Some(
+            r#"_{bin}_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local values
+    values="$({bin} complete-resources -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null)"
+
+    if [[ -n "$values" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$values" -- "$cur"))
+        return 0
+    fi
+
+    _{bin} "$@"
+}
+
+complete -F _{bin}_dynamic -o nosort -o bashdefault -o default {bin}"#,
+        ),
+
+        CompletionShell::Zsh => Some(
+            r#"_{bin}_dynamic() {
+    local -a values
+    values=("${(@f)$({bin} complete-resources -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+
+    if [[ -n "${values[1]}" ]]; then
+        compadd -a values
+    else
+        _{bin} "$@"
+    fi
+}
+
+compdef _{bin}_dynamic {bin}"#,
+        ),
+
+        CompletionShell::Fish => Some(
+            r#"complete -c {bin} -a '({bin} complete-resources -- (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)'"#,
+        ),
+
+        _ => None,
+    }
 }
@@ -0,0 +1,47 @@
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+use tokio::fs;
+use tokio::process::Command;
+
+use super::utils::config_path;
+use crate::state::State;
+use crate::store::config::{read_table, Config};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Open the config in your editor")]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        long,
+        help = "Edit the config of the current project instead of the user config"
+    )]
+    pub local: bool,
+}
+
+pub async fn handle(options: &Options, state: &State) -> Result<()> {
+    let path = config_path(options.local)?;
+
+    if fs::metadata(&path).await.is_err() {
+        fs::create_dir_all(path.parent().context("Failed to get config directory")?).await?;
+        fs::write(&path, "").await?;
+    }
+
+    let editor = state.config.editor();
+    let mut args = editor.split_whitespace();
+
+    let status = Command::new(args.next().context("No editor configured")?)
+        .args(args)
+        .arg(&path)
+        .status()
+        .await
+        .with_context(|| format!("Failed to open `{editor}`"))?;
+
+    ensure!(status.success(), "Editor exited with {status}");
+
+    // surface mistakes right away instead of on the next command
+    Config::from_table(read_table(&path).await?)?;
+
+    log::info!("Saved {}", path.display());
+
+    Ok(())
+}
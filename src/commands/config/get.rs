@@ -0,0 +1,29 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use super::utils::format_value;
+use crate::state::State;
+use crate::store::config::get_key;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Get the value of a config key, e.g. `logs.lines`")]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Key to get, sections are separated by dots")]
+    pub key: String,
+}
+
+pub fn handle(options: &Options, state: &State) -> Result<()> {
+    let table = state.config.to_table()?;
+
+    let value =
+        get_key(&table, &options.key).with_context(|| format!("`{}` is not set", options.key))?;
+
+    if let Some(output) = state.output.serialize(value)? {
+        println!("{output}");
+    } else {
+        println!("{}", format_value(value));
+    }
+
+    Ok(())
+}
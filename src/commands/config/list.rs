@@ -0,0 +1,29 @@
+use anyhow::Result;
+use clap::Parser;
+
+use super::utils::format_config;
+use crate::state::State;
+use crate::store::config::flatten;
+
+#[derive(Debug, Parser)]
+#[clap(about = "List all config values, including the ones of the current project")]
+#[group(skip)]
+pub struct Options {}
+
+pub fn handle(_options: &Options, state: &State) -> Result<()> {
+    if let Some(output) = state.output.serialize(&state.config)? {
+        println!("{output}");
+
+        return Ok(());
+    }
+
+    let values = flatten(&state.config.to_table()?);
+
+    if values.is_empty() {
+        log::info!("No config values are set");
+    } else {
+        println!("{}", format_config(&values, true).join("\n"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+mod edit;
+mod get;
+mod list;
+mod render;
+mod set;
+mod utils;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::state::State;
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    Get(get::Options),
+    Set(set::Options),
+    #[clap(name = "ls", alias = "list")]
+    List(list::Options),
+    Edit(edit::Options),
+    Render(render::Options),
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Manage the configuration in `~/.hop/config.toml`")]
+#[group(skip)]
+pub struct Options {
+    #[clap(subcommand)]
+    pub commands: Commands,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    match options.commands {
+        Commands::Get(options) => get::handle(&options, &state),
+        Commands::Set(options) => set::handle(options).await,
+        Commands::List(options) => list::handle(&options, &state),
+        Commands::Edit(options) => edit::handle(&options, &state).await,
+        Commands::Render(options) => render::handle(options).await,
+    }
+}
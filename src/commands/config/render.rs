@@ -0,0 +1,69 @@
+use std::env::current_dir;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::store::hopfile::{self, HopFile};
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Print the hopfile with its variables replaced",
+    long_about = "Print the hopfile with its variables replaced\n\n`${VAR}`, `${VAR:-default}` and `${VAR:?error}` are taken from the environment or the `.env` file next to the hopfile. With `--environment` the overrides of the environment are applied too."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        name = "path",
+        help = "Hopfile or directory with the hopfile, defaults to current directory"
+    )]
+    pub path: Option<PathBuf>,
+}
+
+pub async fn handle(options: Options) -> Result<()> {
+    let mut path = current_dir()?;
+
+    if let Some(option) = options.path {
+        path = path.join(option);
+    }
+
+    let path = HopFile::find_file(path)?;
+    let content = HopFile::read(&path).await?;
+    let variables = HopFile::variables(&path).await?;
+
+    let json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+
+    let rendered = match hopfile::environment() {
+        Some(environment) => {
+            let mut hopfile = HopFile::deserialize_environment(
+                path.clone(),
+                &content,
+                &variables,
+                Some(&environment),
+            )?;
+
+            // the overrides are already applied
+            hopfile.environments.clear();
+
+            if json {
+                serde_json::to_string_pretty(&hopfile)?
+            } else {
+                serde_yaml::to_string(&hopfile)?
+            }
+        }
+
+        None => {
+            let value = HopFile::resolve(&path, &content, &variables)?;
+
+            if json {
+                serde_json::to_string_pretty(&value)?
+            } else {
+                serde_yaml::to_string(&value)?
+            }
+        }
+    };
+
+    println!("{}", rendered.trim_end());
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use clap::Parser;
+
+use super::utils::config_path;
+use crate::store::config::{parse_value, read_table, set_key, write_table, Config};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Set the value of a config key, e.g. `hop config set deploy.mode local`")]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Key to set, sections are separated by dots")]
+    pub key: String,
+
+    #[clap(help = "Value to set, parsed as TOML and used as a string otherwise")]
+    pub value: String,
+
+    #[clap(
+        long,
+        help = "Write to the config of the current project instead of the user config"
+    )]
+    pub local: bool,
+}
+
+pub async fn handle(options: Options) -> Result<()> {
+    let path = config_path(options.local)?;
+
+    let mut table = read_table(&path).await?;
+
+    set_key(&mut table, &options.key, parse_value(&options.value))?;
+
+    // make sure the key exists and the value has the right type
+    Config::from_table(table.clone())?;
+
+    write_table(&path, &table).await?;
+
+    log::info!("Set `{}` in {}", options.key, path.display());
+
+    Ok(())
+}
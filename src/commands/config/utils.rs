@@ -0,0 +1,46 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use tabwriter::TabWriter;
+use toml::Value;
+
+use crate::config::EXEC_NAME;
+use crate::store::config::Config;
+
+/// Config file to write to, the project one is next to the Hopfile
+pub fn config_path(local: bool) -> Result<PathBuf> {
+    if local {
+        Config::project_path().with_context(|| {
+            format!("No Hopfile found, run `{EXEC_NAME} link` to link this directory first")
+        })
+    } else {
+        Config::user_path()
+    }
+}
+
+/// Formats a value without quotes around strings
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+pub fn format_config(values: &[(String, Value)], title: bool) -> Vec<String> {
+    let mut tw = TabWriter::new(vec![]);
+
+    if title {
+        writeln!(&mut tw, "KEY\tVALUE").unwrap();
+    }
+
+    for (key, value) in values {
+        writeln!(&mut tw, "{key}\t{}", format_value(value)).unwrap();
+    }
+
+    String::from_utf8(tw.into_inner().unwrap())
+        .unwrap()
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect()
+}
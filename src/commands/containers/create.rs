@@ -4,12 +4,13 @@ use clap::Parser;
 use crate::commands::containers::utils::create_containers;
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Create containers for a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(short, long, help = "ID of the deployment")]
+    #[clap(short, long, help = "Name or ID of the deployment")]
     deployment: Option<String>,
 
     #[clap(help = "Number of containers to create")]
@@ -18,7 +19,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -27,7 +28,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("--deployment")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -39,7 +40,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     let count = match options.count {
         Some(count) => count,
-        None => dialoguer::Input::<u64>::new()
+        None => prompt::Input::<u64>::new("<COUNT>")
             .with_prompt("Number of containers to create")
             .interact()?,
     };
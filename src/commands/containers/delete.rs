@@ -4,13 +4,15 @@ use clap::Parser;
 use super::utils::delete_container;
 use crate::commands::containers::utils::{format_containers, get_all_containers};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete containers")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "IDs of the containers")]
+    #[clap(help = "IDs or `<deployment>/<index>` of the containers")]
     containers: Vec<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -19,7 +21,13 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let containers = if !options.containers.is_empty() {
-        options.containers
+        let mut ids = vec![];
+
+        for reference in &options.containers {
+            ids.push(state.get_container_by_name_or_id(reference).await?.id);
+        }
+
+        ids
     } else {
         let project_id = state.ctx.current_project_error()?.id;
 
@@ -27,7 +35,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<CONTAINERS>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -37,7 +45,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!containers.is_empty(), "No containers found");
         let containers_fmt = format_containers(&containers, false);
 
-        let idxs = dialoguer::MultiSelect::new()
+        let idxs = prompt::MultiSelect::new("<CONTAINERS>")
             .with_prompt("Select containers to delete")
             .items(&containers_fmt)
             .interact()?;
@@ -51,7 +59,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete {} containers?",
                 containers.len()
@@ -59,7 +67,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     let mut delete_count = 0;
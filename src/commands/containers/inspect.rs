@@ -4,23 +4,23 @@ use anyhow::{ensure, Result};
 use clap::Parser;
 use tabwriter::TabWriter;
 
-use super::utils::{format_containers, get_all_containers, get_container, UNAVAILABLE_ELEMENT};
+use super::utils::{format_containers, get_all_containers, UNAVAILABLE_ELEMENT};
 use crate::commands::containers::utils::format_single_metrics;
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
 use crate::state::State;
-use crate::utils::relative_time;
+use crate::utils::{prompt, relative_time};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Inspect a container")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the container")]
+    #[clap(help = "ID or `<deployment>/<index>` of the container")]
     pub container: Option<String>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
-    let (container, deployment) = if let Some(container_id) = options.container {
-        let container = get_container(&state.http, &container_id).await?;
+    let (container, deployment) = if let Some(reference) = options.container {
+        let container = state.get_container_by_name_or_id(&reference).await?;
         let deployment = get_deployment(&state.http, &container.deployment_id).await?;
 
         (container, deployment)
@@ -31,7 +31,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<CONTAINER>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -43,7 +43,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!containers.is_empty(), "No containers found");
         let containers_fmt = format_containers(&containers, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<CONTAINER>")
             .with_prompt("Select container")
             .items(&containers_fmt)
             .default(0)
@@ -52,6 +52,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         (containers[idx].to_owned(), deployment)
     };
 
+    if let Some(output) = state.output.serialize(&container)? {
+        println!("{output}");
+
+        return Ok(());
+    }
+
     let mut tw = TabWriter::new(vec![]);
 
     writeln!(tw, "{}", container.id)?;
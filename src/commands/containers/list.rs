@@ -1,24 +1,33 @@
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use crate::commands::containers::utils::{format_containers, get_all_containers};
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
+use crate::commands::containers::utils::{format_containers, get_containers};
+use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::watch::{self, WatchEvent, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "List all containers")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(short, long, help = "Only print the IDs of the deployments")]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub limit: LimitOptions,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment = match options.deployment {
-        Some(id) => get_deployment(&state.http, &id).await?,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -27,7 +36,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -37,21 +46,29 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let containers = get_all_containers(&state.http, &deployment.id).await?;
-
-    if options.quiet {
-        let ids = containers
-            .iter()
-            .map(|d| d.id.as_str())
-            .collect::<Vec<_>>()
-            .join(" ");
+    let (http, output, deployment_id) = (&state.http, state.output, &deployment.id);
 
-        println!("{ids}");
-    } else {
-        let containers_fmt = format_containers(&containers, true);
+    watch::render(
+        &state,
+        options.watch,
+        &[WatchEvent::Containers],
+        || async move {
+            let containers = get_containers(http, deployment_id, options.limit.into()).await?;
 
-        println!("{}", containers_fmt.join("\n"));
-    }
+            if let Some(output) = output.serialize(&containers)? {
+                Ok(vec![output])
+            } else if options.quiet {
+                let ids = containers
+                    .iter()
+                    .map(|d| d.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
 
-    Ok(())
+                Ok(vec![ids])
+            } else {
+                Ok(format_containers(&containers, true))
+            }
+        },
+    )
+    .await
 }
@@ -8,28 +8,22 @@ use tokio::process::Command;
 
 use super::utils::{format_containers, format_logs, get_all_containers, get_container_logs};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
-use crate::config::DEFAULT_EDITOR;
 use crate::state::State;
 use crate::utils::arisu::{ArisuClient, ArisuMessage};
-use crate::utils::in_path;
+use crate::utils::{in_path, prompt};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Get logs of a container")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the container")]
+    #[clap(help = "ID or `<deployment>/<index>` of the container")]
     container: Option<String>,
 
     #[clap(short, long, help = "Follow the logs")]
     follow: bool,
 
-    #[clap(
-        short = 'n',
-        long,
-        help = "Number of lines to show",
-        default_value = "10"
-    )]
-    lines: u64,
+    #[clap(short = 'n', long, help = "Number of lines to show [default: 10]")]
+    lines: Option<u64>,
 
     #[clap(short, long, help = "Show the newest entries first")]
     reverse: bool,
@@ -41,9 +35,14 @@ pub struct Options {
     details: bool,
 }
 
-pub async fn handle(options: Options, state: State) -> Result<()> {
+pub async fn handle(mut options: Options, state: State) -> Result<()> {
+    let defaults = &state.config.logs;
+
+    options.timestamps |= defaults.timestamps.unwrap_or(false);
+    options.details |= defaults.details.unwrap_or(false);
+
     let container = match options.container {
-        Some(id) => id,
+        Some(reference) => state.get_container_by_name_or_id(&reference).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -52,7 +51,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<CONTAINER>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -62,7 +61,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!containers.is_empty(), "No containers found");
             let containers_fmt = format_containers(&containers, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<CONTAINER>")
                 .with_prompt("Select a container")
                 .default(0)
                 .items(&containers_fmt)
@@ -76,7 +75,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let logs = get_container_logs(
         &state.http,
         &container,
-        options.lines,
+        options.lines.or(defaults.lines).unwrap_or(10),
         // doesnt make sense to follow reversed logs
         if options.reverse && !options.follow {
             "asc"
@@ -86,7 +85,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     )
     .await?;
 
-    if !options.follow {
+    if !options.follow && prompt::is_interactive() {
         let temp = temp_dir().join(format!("hop_ignite_logs-{container}.txt"));
 
         fs::write(
@@ -95,12 +94,10 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         )
         .await?;
 
-        let editor = if in_path("less").await {
+        let editor = if state.config.editor.is_none() && in_path("less").await {
             "less".to_string()
         } else {
-            std::env::var("EDITOR")
-                .or_else(|_| std::env::var("VISUAL"))
-                .unwrap_or_else(|_| DEFAULT_EDITOR.to_string())
+            state.config.editor()
         };
 
         log::info!("Opening logs in `{editor}`");
@@ -119,6 +116,10 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         format_logs(&logs, true, options.timestamps, options.details).join("\n")
     );
 
+    if !options.follow {
+        return Ok(());
+    }
+
     let token = state.token().context("No token found")?;
 
     let mut arisu = ArisuClient::new(&container, &token).await?;
@@ -5,17 +5,18 @@ use clap::Parser;
 use console::Term;
 use futures_util::StreamExt;
 
-use super::utils::{format_containers, get_all_containers, get_container};
+use super::utils::{format_containers, get_all_containers};
 use crate::commands::containers::utils::format_single_metrics;
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
 use crate::state::State;
 use crate::utils::arisu::{ArisuClient, ArisuMessage};
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Get metrics for a container")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the container")]
+    #[clap(help = "ID or `<deployment>/<index>` of the container")]
     pub container: Option<String>,
 
     #[clap(short, long, help = "Show metrics in real time")]
@@ -23,8 +24,8 @@ pub struct Options {
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
-    let (container, deployment) = if let Some(container_id) = options.container {
-        let container = get_container(&state.http, &container_id).await?;
+    let (container, deployment) = if let Some(reference) = options.container {
+        let container = state.get_container_by_name_or_id(&reference).await?;
         let deployment = get_deployment(&state.http, &container.deployment_id).await?;
 
         (container, deployment)
@@ -35,7 +36,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<CONTAINER>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -47,7 +48,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!containers.is_empty(), "No containers found");
         let containers_fmt = format_containers(&containers, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<CONTAINER>")
             .with_prompt("Select container")
             .items(&containers_fmt)
             .default(0)
@@ -3,11 +3,15 @@ use std::str::FromStr;
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::ignite::types::Deployment;
+use crate::impl_paginated;
+use crate::utils::resolve::Resolvable;
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ContainerType {
     Ephemeral,
@@ -55,11 +59,11 @@ impl Display for ContainerState {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Uptime {
     pub last_start: Option<DateTime<Utc>>,
 }
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Container {
     pub id: String,
     pub created_at: DateTime<Utc>,
@@ -73,12 +77,22 @@ pub struct Container {
     pub type_: ContainerType,
 }
 
+impl Resolvable for Container {
+    const KIND: &'static str = "container";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 #[derive(Debug, Deserialize)]
 
 pub struct MultipleContainersResponse {
     pub containers: Vec<Container>,
 }
 
+impl_paginated!(MultipleContainersResponse => containers: Container);
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ContainerOptions {
     pub containers: Option<u64>,
@@ -115,6 +129,8 @@ pub struct LogsResponse {
     pub logs: Vec<Log>,
 }
 
+impl_paginated!(LogsResponse => logs: Log);
+
 #[derive(Debug, Deserialize)]
 pub struct SingleContainer {
     pub container: Container,
@@ -140,6 +156,8 @@ impl Metrics {
     }
 }
 
+// the variants are named after the Leap events
+#[allow(dead_code, clippy::enum_variant_names)]
 #[derive(Debug, Deserialize)]
 #[serde(tag = "e", content = "d", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContainerEvents {
@@ -147,4 +165,8 @@ pub enum ContainerEvents {
         container_id: String,
         metrics: Metrics,
     },
+    // only the event itself is used, to know when to refresh
+    ContainerCreate(IgnoredAny),
+    ContainerUpdate(IgnoredAny),
+    ContainerDelete(IgnoredAny),
 }
@@ -11,6 +11,7 @@ use super::types::{
     MultipleContainersResponse, SingleContainer,
 };
 use crate::commands::ignite::types::Deployment;
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
 use crate::utils::relative_time;
 use crate::utils::size::{parse_size, user_friendly_size};
@@ -61,16 +62,20 @@ pub async fn get_container(http: &HttpClient, container_id: &str) -> Result<Cont
 }
 
 pub async fn get_all_containers(http: &HttpClient, deployment_id: &str) -> Result<Vec<Container>> {
-    let response = http
-        .request::<MultipleContainersResponse>(
-            "GET",
-            &format!("/ignite/deployments/{deployment_id}/containers"),
-            None,
-        )
-        .await?
-        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+    get_containers(http, deployment_id, PageOptions::default()).await
+}
 
-    Ok(response.containers)
+pub async fn get_containers(
+    http: &HttpClient,
+    deployment_id: &str,
+    page: PageOptions,
+) -> Result<Vec<Container>> {
+    http.request_all::<MultipleContainersResponse>(
+        &format!("/ignite/deployments/{deployment_id}/containers"),
+        PageStyle::Unpaginated,
+        page,
+    )
+    .await
 }
 
 pub async fn get_container_logs(
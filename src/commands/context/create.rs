@@ -0,0 +1,59 @@
+use anyhow::{ensure, Result};
+use clap::Parser;
+
+use crate::config::EXEC_NAME;
+use crate::state::State;
+use crate::store::context::NamedContext;
+use crate::store::Store;
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Create a new context, the project is taken from `--project` or the current project"
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Name of the context")]
+    pub name: String,
+
+    #[clap(short, long, help = "ID of the user, defaults to the current user")]
+    pub user: Option<String>,
+
+    #[clap(long, help = "URL of the API to use")]
+    pub api_url: Option<String>,
+
+    #[clap(long = "use", help = "Switch to the context after creating it")]
+    pub use_: bool,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    let user = options.user.or_else(|| state.ctx.default_user.clone());
+
+    if let Some(user) = &user {
+        state.auth.unlock().await?;
+
+        ensure!(
+            state.auth.authorized.contains_key(user),
+            "You are not logged in as `{user}`, run `{EXEC_NAME} auth login` to login"
+        );
+    }
+
+    state.ctx.create_context(
+        &options.name,
+        NamedContext {
+            user,
+            project: state.ctx.project_override.clone(),
+            api_url: options.api_url,
+        },
+    )?;
+
+    if options.use_ {
+        state.ctx.select_context(&options.name)?;
+        state.ctx.current_context = options.name.clone();
+    }
+
+    state.ctx.save().await?;
+
+    log::info!("Created context `{}`", options.name);
+
+    Ok(())
+}
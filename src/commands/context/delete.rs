@@ -0,0 +1,22 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::state::State;
+use crate::store::Store;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Delete a context")]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Name of the context")]
+    pub name: String,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    state.ctx.remove_context(&options.name)?;
+    state.ctx.save().await?;
+
+    log::info!("Deleted context `{}`", options.name);
+
+    Ok(())
+}
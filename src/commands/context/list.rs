@@ -0,0 +1,35 @@
+use anyhow::Result;
+use clap::Parser;
+
+use super::utils::format_contexts;
+use crate::state::State;
+
+#[derive(Debug, Parser)]
+#[clap(about = "List all contexts")]
+#[group(skip)]
+pub struct Options {
+    #[clap(short, long, help = "Only print the names of the contexts")]
+    pub quiet: bool,
+}
+
+pub fn handle(options: &Options, state: &State) -> Result<()> {
+    let contexts = state.ctx.all_contexts();
+
+    if let Some(output) = state.output.serialize(&contexts)? {
+        println!("{output}");
+    } else if options.quiet {
+        let names = contexts
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        println!("{names}");
+    } else {
+        let contexts_fmt = format_contexts(&contexts, &state.ctx.selected_context, true);
+
+        println!("{}", contexts_fmt.join("\n"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+mod create;
+mod delete;
+mod list;
+mod rename;
+mod select;
+mod utils;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::state::State;
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    #[clap(name = "ls", alias = "list")]
+    List(list::Options),
+    #[clap(name = "use", alias = "switch")]
+    Use(select::Options),
+    #[clap(name = "new", alias = "create")]
+    Create(create::Options),
+    #[clap(name = "rm", alias = "delete")]
+    Delete(delete::Options),
+    Rename(rename::Options),
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Manage named contexts of user, project and API URL")]
+#[group(skip)]
+pub struct Options {
+    #[clap(subcommand)]
+    pub commands: Commands,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    match options.commands {
+        Commands::List(options) => list::handle(&options, &state),
+        Commands::Use(options) => select::handle(options, state).await,
+        Commands::Create(options) => create::handle(options, state).await,
+        Commands::Delete(options) => delete::handle(options, state).await,
+        Commands::Rename(options) => rename::handle(options, state).await,
+    }
+}
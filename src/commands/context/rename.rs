@@ -0,0 +1,29 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::state::State;
+use crate::store::Store;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Rename a context")]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Current name of the context")]
+    pub name: String,
+
+    #[clap(help = "New name of the context")]
+    pub new_name: String,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    state.ctx.rename_context(&options.name, &options.new_name)?;
+    state.ctx.save().await?;
+
+    log::info!(
+        "Renamed context `{}` to `{}`",
+        options.name,
+        options.new_name
+    );
+
+    Ok(())
+}
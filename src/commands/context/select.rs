@@ -0,0 +1,45 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::state::State;
+use crate::store::Store;
+use crate::utils::prompt;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Switch to a different context")]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Name of the context to use")]
+    pub name: Option<String>,
+}
+
+pub async fn handle(options: Options, mut state: State) -> Result<()> {
+    let name = match options.name {
+        Some(name) => name,
+
+        None => {
+            let names = state.ctx.contexts.keys().cloned().collect::<Vec<_>>();
+
+            let idx = prompt::Select::new("<NAME>")
+                .with_prompt("Select a context")
+                .items(&names)
+                .default(
+                    names
+                        .iter()
+                        .position(|name| name == &state.ctx.current_context)
+                        .unwrap_or(0),
+                )
+                .interact()?;
+
+            names[idx].clone()
+        }
+    };
+
+    state.ctx.select_context(&name)?;
+    state.ctx.current_context = name.clone();
+    state.ctx.save().await?;
+
+    log::info!("Switched to context `{name}`");
+
+    Ok(())
+}
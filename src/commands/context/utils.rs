@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use tabwriter::TabWriter;
+
+use crate::store::context::NamedContext;
+
+pub fn format_contexts(
+    contexts: &BTreeMap<String, NamedContext>,
+    current: &str,
+    title: bool,
+) -> Vec<String> {
+    let mut tw = TabWriter::new(vec![]);
+
+    if title {
+        writeln!(&mut tw, "CURRENT\tNAME\tUSER\tPROJECT\tAPI URL").unwrap();
+    }
+
+    for (name, context) in contexts {
+        writeln!(
+            &mut tw,
+            "{}\t{name}\t{}\t{}\t{}",
+            if name == current { "*" } else { "" },
+            context.user.as_deref().unwrap_or("-"),
+            context.project.as_deref().unwrap_or("-"),
+            context.api_url.as_deref().unwrap_or("-"),
+        )
+        .unwrap();
+    }
+
+    String::from_utf8(tw.into_inner().unwrap())
+        .unwrap()
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect()
+}
@@ -1,7 +1,8 @@
-mod types;
-mod util;
+pub mod types;
+pub mod util;
 
 use std::path::PathBuf;
+use std::sync::{Mutex, Once};
 
 use anyhow::{bail, Result};
 use leap_client_rs::leap::types::Event;
@@ -12,63 +13,162 @@ use tokio::{fs, spawn};
 use self::types::BuildEvents;
 use self::util::{builder_post, compress};
 use crate::commands::deploy::builder::types::BuildStatus;
-use crate::commands::ignite::builds::utils::cancel_build;
+use crate::commands::ignite::builds::types::BuildState;
+use crate::commands::ignite::builds::utils::{cancel_build, get_all_builds};
+use crate::state::http::HttpClient;
 use crate::state::State;
-use crate::utils::urlify;
+use crate::store::cache::BuildCache;
+use crate::store::Store;
+use crate::utils::size::user_friendly_size;
+use crate::utils::{logging, urlify};
+
+/// Builds that are still running, they are cancelled when the user presses Ctrl+C
+static RUNNING_BUILDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static CTRLC_HANDLER: Once = Once::new();
+
+/// Removes the build from [`RUNNING_BUILDS`] however the build ends
+struct RunningBuild(String);
+
+impl RunningBuild {
+    fn new(build_id: &str) -> Self {
+        if let Ok(mut builds) = RUNNING_BUILDS.lock() {
+            builds.push(build_id.to_string());
+        }
+
+        Self(build_id.to_string())
+    }
+}
+
+impl Drop for RunningBuild {
+    fn drop(&mut self) {
+        if let Ok(mut builds) = RUNNING_BUILDS.lock() {
+            builds.retain(|build_id| build_id != &self.0);
+        }
+    }
+}
+
+/// The handler can only be set once, so it cancels every running build
+fn cancel_builds_on_ctrlc(http: &HttpClient) -> Result<()> {
+    let mut result = Ok(());
+
+    CTRLC_HANDLER.call_once(|| {
+        let (tx, mut rx) = unbounded_channel();
+
+        let http = http.clone();
+
+        spawn(async move {
+            if rx.recv().await.is_none() {
+                return;
+            }
+
+            let builds = RUNNING_BUILDS
+                .lock()
+                .map(|builds| builds.clone())
+                .unwrap_or_default();
+
+            for build_id in builds {
+                log::info!("Cancelling build...");
+
+                if cancel_build(&http, &build_id).await.is_ok() {
+                    log::info!("Build cancelled by user");
+                } else {
+                    log::error!("Failed to cancel build");
+                }
+            }
+
+            std::process::exit(1);
+        });
+
+        result = ctrlc::set_handler(move || {
+            tx.send(()).ok();
+        })
+        .map_err(Into::into);
+    });
+
+    result
+}
+
+#[derive(Debug, Default)]
+pub struct BuildOptions<'a> {
+    /// Prefix of the build logs when multiple deployments are built at once
+    pub label: Option<&'a str>,
+    /// Skip the build when the files did not change since the last release
+    pub skip_unchanged: bool,
+}
+
+/// Build pushed by the Hop builder, recorded with [`record_build`] once it is released
+#[derive(Debug)]
+pub struct PushedBuild {
+    pub build_id: String,
+    pub hash: String,
+}
 
+/// Builds the directory on the Hop builder, returns `None` when the build was skipped
 pub async fn build(
     state: &State,
     project_id: &str,
     deployment_id: &str,
     dir: PathBuf,
     leap: &mut LeapEdge,
-) -> Result<()> {
-    // deployment id is used not to colide if the user is deploying multiple items
-    let packed = compress(deployment_id, dir).await?;
+    options: &BuildOptions<'_>,
+) -> Result<Option<PushedBuild>> {
+    let prefix = options
+        .label
+        .map(|label| format!("[{label}] "))
+        .unwrap_or_default();
 
-    log::info!("Packed to: {packed}");
+    // deployment id is used not to colide if the user is deploying multiple items
+    let archive = compress(deployment_id, dir).await?;
 
-    let bytes = fs::read(packed.clone()).await?;
+    log::info!("Packed to: {}", archive.path.display());
+    log::debug!("Content hash: {}", archive.hash);
 
-    log::info!("Uploading...");
+    if options.skip_unchanged {
+        if let Some(build_id) = unchanged_build(state, deployment_id, &archive.hash).await? {
+            log::info!("{prefix}No changes since build `{build_id}`, skipping");
 
-    let build = builder_post(&state.http, deployment_id, bytes).await?;
+            fs::remove_file(archive.path).await?;
 
-    let (tx, mut rx) = unbounded_channel();
+            return Ok(None);
+        }
+    }
 
-    let http = state.http.clone();
-    let build_id = build.id.clone();
+    let size = fs::metadata(&archive.path).await?.len();
+    let max_size = state.config.deploy.max_upload_size()?;
 
-    spawn(async move {
-        loop {
-            match rx.recv().await {
-                Some("CANCEL") => {
-                    log::info!("Cancelling build...");
+    if size > max_size {
+        fs::remove_file(&archive.path).await?;
 
-                    if cancel_build(&http, &build_id).await.is_ok() {
-                        log::info!("Build cancelled by user");
-                    } else {
-                        log::error!("Failed to cancel build");
-                    }
+        bail!(
+            "The archive is {}, over the upload limit of {}. Exclude files with a `.hopignore` or raise `deploy.max_upload_size` in the config",
+            user_friendly_size(size)?,
+            user_friendly_size(max_size)?
+        );
+    }
 
-                    std::process::exit(1);
-                }
+    // progress bars of parallel builds would overwrite each other
+    let build = builder_post(
+        &state.http,
+        deployment_id,
+        &archive.path,
+        options.label.is_none(),
+    )
+    .await;
+
+    if build.is_err() {
+        fs::remove_file(&archive.path).await?;
+    }
 
-                Some("OK") => break,
+    let build = build?;
 
-                _ => {}
-            }
-        }
-    });
+    logging::set_context("build", &build.id);
 
-    let ctrlc = tx.clone();
+    let _running = RunningBuild::new(&build.id);
 
-    ctrlc::set_handler(move || {
-        ctrlc.send("CANCEL").ok();
-    })?;
+    cancel_builds_on_ctrlc(&state.http)?;
 
     log::info!("Deleting archive...");
-    fs::remove_file(packed).await?;
+    fs::remove_file(&archive.path).await?;
 
     log::info!("From Hop builder:");
 
@@ -78,14 +178,15 @@ pub async fn build(
                 continue;
             }
 
-            let Ok(build_data) = serde_json::from_value(serde_json::to_value(capsuled.data)?) else {
+            let Ok(build_data) = serde_json::from_value(serde_json::to_value(capsuled.data)?)
+            else {
                 continue;
             };
 
             match build_data {
                 BuildEvents::BuildCreate(build_create) => {
                     if build_create.build.id == build.id {
-                        println!("Validating build...");
+                        println!("{prefix}Validating build...");
                     }
                 }
 
@@ -96,11 +197,12 @@ pub async fn build(
                             BuildStatus::Validating => {}
 
                             BuildStatus::Pending => {
-                                println!("Build has been successfully validated, building...");
+                                println!(
+                                    "{prefix}Build has been successfully validated, building..."
+                                );
                             }
 
                             BuildStatus::ValidationFailed => {
-                                tx.send("OK").ok();
                                 leap.close().await;
 
                                 // this **should** be present if the status is validation failed
@@ -118,13 +220,18 @@ pub async fn build(
 
                 BuildEvents::BuildProgress(build_progress) => {
                     if build_progress.build_id == build.id {
-                        print!("{}", build_progress.log);
+                        if prefix.is_empty() {
+                            print!("{}", build_progress.log);
+                        } else {
+                            for line in build_progress.log.lines() {
+                                println!("{prefix}{line}");
+                            }
+                        }
                     }
                 }
 
                 BuildEvents::BuildCancelled(build_cancelled) => {
                     if build_cancelled.build_id == build.id {
-                        tx.send("OK").ok();
                         leap.close().await;
 
                         bail!("Build cancelled");
@@ -133,13 +240,14 @@ pub async fn build(
 
                 BuildEvents::PushSuccess(build_complete) => {
                     if build_complete.build_id == build.id {
-                        tx.send("OK").ok();
-
                         println!();
 
-                        log::info!("Build complete");
+                        log::info!("{prefix}Build complete");
 
-                        return Ok(());
+                        return Ok(Some(PushedBuild {
+                            build_id: build.id,
+                            hash: archive.hash,
+                        }));
                     }
                 }
 
@@ -161,7 +269,48 @@ pub async fn build(
         }
     }
 
-    tx.send("OK").ok();
+    Ok(Some(PushedBuild {
+        build_id: build.id,
+        hash: archive.hash,
+    }))
+}
+
+/// The last successful build of the deployment if it was built from the same files
+async fn unchanged_build(state: &State, deployment_id: &str, hash: &str) -> Result<Option<String>> {
+    let cache = BuildCache::new().await?;
+
+    let Some(record) = cache
+        .get(deployment_id)
+        .filter(|record| record.hash == hash)
+    else {
+        return Ok(None);
+    };
+
+    // the deployment could have been built from somewhere else since
+    let latest = get_all_builds(&state.http, deployment_id)
+        .await?
+        .into_iter()
+        .find(|build| matches!(build.state, BuildState::Succeeded));
+
+    Ok(latest
+        .filter(|build| build.id == record.build_id)
+        .map(|build| build.id))
+}
 
-    Ok(())
+/// Remembers the files the deployment was released from for `--skip-unchanged`
+pub async fn record_build(deployment_id: &str, build: &PushedBuild) {
+    let result = async {
+        let mut cache = BuildCache::new().await?;
+        cache.insert(
+            deployment_id.to_string(),
+            build.build_id.clone(),
+            build.hash.clone(),
+        );
+        cache.save().await
+    }
+    .await;
+
+    if let Err(error) = result {
+        log::debug!("Could not record build: {error}");
+    }
 }
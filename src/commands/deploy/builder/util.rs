@@ -1,24 +1,60 @@
 use std::env::temp_dir;
+use std::fs::Metadata;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context as _, Result};
 use async_compression::tokio::write::GzipEncoder;
+use futures_util::TryStreamExt;
 use hyper::Method;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use ignore::WalkBuilder;
+use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::multipart::{Form, Part};
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
-use tokio_tar::Builder as TarBuilder;
+use reqwest::Body;
+use sha2::{Digest, Sha256};
+use tokio::fs::{self, File};
+use tokio::io::{empty, AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
+use tokio_tar::{Builder as TarBuilder, EntryType, Header};
+use tokio_util::io::ReaderStream;
 
 use super::types::{Build, SingleBuild};
 use crate::commands::deploy::HOP_BUILD_BASE_URL;
 use crate::state::http::HttpClient;
 use crate::store::hopfile::VALID_HOP_FILENAMES;
+use crate::utils::size::user_friendly_size;
+
+/// Streams the archive to the builder, with a progress bar when `show_progress` is set
+pub async fn builder_post(
+    http: &HttpClient,
+    deployment_id: &str,
+    archive: &Path,
+    show_progress: bool,
+) -> Result<Build> {
+    let file = File::open(archive).await?;
+    let size = file.metadata().await?.len();
+
+    let progress = if show_progress {
+        ProgressBar::new(size)
+    } else {
+        ProgressBar::hidden()
+    };
+
+    progress.set_style(
+        ProgressStyle::with_template(
+            "Uploading [{bar:30}] {bytes}/{total_bytes} ({binary_bytes_per_sec}, {eta})",
+        )?
+        .progress_chars("=> "),
+    );
+
+    let uploaded = progress.clone();
+    let stream = ReaderStream::new(file).inspect_ok(move |chunk| uploaded.inc(chunk.len() as u64));
 
-pub async fn builder_post(http: &HttpClient, deployment_id: &str, bytes: Vec<u8>) -> Result<Build> {
     let multipart = Form::new().part(
         "file",
-        Part::bytes(bytes)
+        Part::stream_with_length(Body::wrap_stream(stream), size)
             .file_name("deployment.tar.gz")
             .mime_str("application/x-gzip")?,
     );
@@ -27,7 +63,7 @@ pub async fn builder_post(http: &HttpClient, deployment_id: &str, bytes: Vec<u8>
         std::env::var("BUILDER_URL").unwrap_or_else(|_| HOP_BUILD_BASE_URL.to_string());
 
     let response = http
-        .client
+        .upload_client()?
         .request(
             Method::POST,
             format!("{builder_uri}/deployments/{deployment_id}/builds",).as_str(),
@@ -35,7 +71,17 @@ pub async fn builder_post(http: &HttpClient, deployment_id: &str, bytes: Vec<u8>
         .header("content_type", "multipart/form-data".to_string())
         .multipart(multipart)
         .send()
-        .await?;
+        .await;
+
+    progress.finish_and_clear();
+
+    let response = response?;
+
+    log::info!(
+        "Uploaded {} in {:.1}s",
+        user_friendly_size(size)?,
+        progress.elapsed().as_secs_f64()
+    );
 
     let build = http
         .handle_response::<SingleBuild>(response)
@@ -56,41 +102,40 @@ static DEFAULT_IGNORE_LIST: &[&str] = &[
     ".vscode",
 ];
 
-// compress stuff
-pub async fn compress(id: &str, base_dir: PathBuf) -> Result<String> {
-    let base_folder_name = Path::new(&id);
-    let archive_path = temp_dir().join(format!("hop_{id}.tar.gz"));
-
-    // tarball gunzip stuff
-    let writer = File::create(archive_path.clone()).await?;
-    let writer = GzipEncoder::new(writer);
-    let mut archive = TarBuilder::new(writer);
-    archive.follow_symlinks(true);
-
-    log::info!("Finding files to compress...");
-
-    let mut walker = WalkBuilder::new(base_dir.clone());
+/// Files of the directory that go into the archive, sorted by their path relative to it.
+/// Respects `.gitignore` and `.hopignore`, and `.dockerignore` when there is a Dockerfile
+pub async fn find_files(base_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut walker = WalkBuilder::new(base_dir);
     walker.add_ignore(create_global_ignore_file().await?);
     walker.add_custom_ignore_filename(".hopignore");
     walker.hidden(false).follow_links(true);
 
-    let walker = walker.build();
+    if base_dir.join("Dockerfile").is_file() {
+        if let Some(dockerignore) = read_dockerignore(base_dir).await? {
+            walker.filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+                let always_sent = entry.depth() == 1
+                    && ["Dockerfile", ".dockerignore"]
+                        .contains(&entry.file_name().to_str().unwrap_or_default());
+
+                // docker always sends these, even when they are ignored
+                always_sent || !dockerignore.matched(entry.path(), is_dir).is_ignore()
+            });
+        }
+    }
+
+    let mut entries = vec![];
 
-    // add all found files to the tarball
-    for entry in walker {
+    for entry in walker.build() {
         match entry {
             Ok(entry) => {
-                log::debug!("Adding {} to tarball", entry.path().display());
-
                 if VALID_HOP_FILENAMES.contains(&entry.file_name().to_str().unwrap()) {
                     continue;
                 }
 
-                let path = entry.path().strip_prefix(&base_dir).unwrap().to_owned();
+                let path = entry.path().strip_prefix(base_dir).unwrap().to_owned();
 
-                archive
-                    .append_path_with_name(entry.path(), &(*base_folder_name).join(&path))
-                    .await?;
+                entries.push((path, entry.into_path()));
             }
             Err(err) => {
                 log::warn!("Error walking: {}", err);
@@ -98,12 +143,184 @@ pub async fn compress(id: &str, base_dir: PathBuf) -> Result<String> {
         }
     }
 
+    // the walking order depends on the file system
+    entries.sort();
+
+    Ok(entries)
+}
+
+/// `.dockerignore` patterns are relative to the build context, unlike `.gitignore`
+/// patterns which match at any depth, so every pattern is anchored to the directory
+async fn read_dockerignore(base_dir: &Path) -> Result<Option<Gitignore>> {
+    let path = base_dir.join(".dockerignore");
+
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).await?;
+    let mut builder = GitignoreBuilder::new(base_dir);
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(pattern) => ("!", pattern.trim()),
+            None => ("", line),
+        };
+
+        let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+
+        builder
+            .add_line(Some(path.clone()), &format!("{negated}/{pattern}"))
+            .with_context(|| format!("Invalid pattern in {}: {line}", path.display()))?;
+    }
+
+    Ok(Some(builder.build()?))
+}
+
+/// Gzipped tarball of a directory, with the hash of the files in it
+pub struct Archive {
+    pub path: PathBuf,
+    pub hash: String,
+}
+
+/// Packs the directory into a tarball under `id`. Entries are sorted and their times,
+/// owners and modes normalized so the same files always give the same archive and hash
+pub async fn compress(id: &str, base_dir: PathBuf) -> Result<Archive> {
+    let base_folder_name = Path::new(&id);
+    let archive_path = temp_dir().join(format!("hop_{id}.tar.gz"));
+
+    log::info!("Finding files to compress...");
+
+    let entries = find_files(&base_dir).await?;
+
+    // tarball gunzip stuff
+    let writer = File::create(archive_path.clone()).await?;
+    let writer = GzipEncoder::new(writer);
+    let mut archive = TarBuilder::new(writer);
+    let mut hasher = Sha256::new();
+
+    for (path, full_path) in entries {
+        log::debug!("Adding {} to tarball", full_path.display());
+
+        let metadata = fs::metadata(&full_path).await?;
+        let name = base_folder_name.join(&path);
+        // the hash does not depend on the platform separator
+        let hashed_name = slash_path(&path);
+
+        let mut header = Header::new_gnu();
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+
+        if metadata.is_dir() {
+            header.set_entry_type(EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+
+            archive.append_data(&mut header, name, empty()).await?;
+
+            hasher.update(format!("d {hashed_name}\0"));
+        } else if metadata.is_file() {
+            let size = metadata.len();
+            let mode = if is_executable(&metadata) {
+                0o755
+            } else {
+                0o644
+            };
+
+            header.set_entry_type(EntryType::Regular);
+            header.set_mode(mode);
+            header.set_size(size);
+
+            hasher.update(format!("f {mode:o} {hashed_name} {size}\0"));
+
+            // a file that grows while packing must not overflow its entry
+            let file = File::open(&full_path).await?.take(size);
+            let mut reader = HashingReader::new(file, &mut hasher);
+
+            archive.append_data(&mut header, name, &mut reader).await?;
+
+            ensure!(
+                reader.read == size,
+                "{} changed while it was being packed",
+                full_path.display()
+            );
+        } else {
+            log::warn!("Skipping {}, it is not a regular file", full_path.display());
+        }
+    }
+
     let mut buff = archive.into_inner().await?;
     buff.shutdown().await?;
     let mut buff = buff.into_inner();
     buff.shutdown().await?;
 
-    Ok(archive_path.to_str().unwrap().into())
+    Ok(Archive {
+        path: archive_path,
+        hash: format!("{:x}", hasher.finalize()),
+    })
+}
+
+/// Hashes everything read through it, so a file is streamed once into both the archive and the hash
+struct HashingReader<'a, R> {
+    inner: R,
+    hasher: &'a mut Sha256,
+    read: u64,
+}
+
+impl<'a, R> HashingReader<'a, R> {
+    fn new(inner: R, hasher: &'a mut Sha256) -> Self {
+        Self {
+            inner,
+            hasher,
+            read: 0,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for HashingReader<'_, R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let this = &mut *self;
+
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+
+        let chunk = &buf.filled()[before..];
+        this.hasher.update(chunk);
+        this.read += chunk.len() as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Relative path with `/` separators on every platform
+pub fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &Metadata) -> bool {
+    false
 }
 
 async fn create_global_ignore_file() -> Result<PathBuf> {
@@ -119,3 +336,118 @@ async fn create_global_ignore_file() -> Result<PathBuf> {
 
     Ok(path)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compress_is_reproducible() {
+        let root = temp_dir().join(format!("hop-compress-{}", std::process::id()));
+
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("Dockerfile"), "FROM scratch").unwrap();
+        std::fs::write(root.join("hop.yml"), "version: 1").unwrap();
+
+        let first = compress("test-compress-1", root.clone()).await.unwrap();
+        let first_bytes = std::fs::read(&first.path).unwrap();
+
+        // rewriting a file changes its mtime but not the archive
+        std::fs::write(root.join("Dockerfile"), "FROM scratch").unwrap();
+
+        let second = compress("test-compress-1", root.clone()).await.unwrap();
+
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(first_bytes, std::fs::read(&second.path).unwrap());
+
+        // hopfiles are not part of the build
+        std::fs::write(root.join("hop.yml"), "version: 2").unwrap();
+
+        assert_eq!(
+            compress("test-compress-2", root.clone())
+                .await
+                .unwrap()
+                .hash,
+            first.hash
+        );
+
+        std::fs::write(root.join("src/main.rs"), "fn main() { }").unwrap();
+
+        assert_ne!(
+            compress("test-compress-3", root.clone())
+                .await
+                .unwrap()
+                .hash,
+            first.hash
+        );
+
+        for id in 1..=3 {
+            std::fs::remove_file(temp_dir().join(format!("hop_test-compress-{id}.tar.gz")))
+                .unwrap();
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_files_with_dockerignore() {
+        let root = temp_dir().join(format!("hop-dockerignore-{}", std::process::id()));
+
+        for dir in [
+            "node_modules/left-pad",
+            "src/node_modules",
+            "tests/fixtures",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        for file in [
+            "node_modules/left-pad/index.js",
+            "src/node_modules/local.js",
+            "tests/fixtures/big.json",
+            "tests/fixtures/keep.json",
+            "src/main.js",
+            "debug.log",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        std::fs::write(
+            root.join(".dockerignore"),
+            "# dependencies\nnode_modules\ntests/fixtures/*\n!tests/fixtures/keep.json\n*.log\nDockerfile\n",
+        )
+        .unwrap();
+
+        let files = || async {
+            find_files(&root)
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|(_, full_path)| full_path.is_file())
+                .map(|(path, _)| slash_path(&path))
+                .collect::<Vec<_>>()
+        };
+
+        // without a Dockerfile the .dockerignore is not used
+        assert!(files()
+            .await
+            .contains(&"node_modules/left-pad/index.js".to_string()));
+
+        std::fs::write(root.join("Dockerfile"), "FROM node").unwrap();
+
+        // patterns are relative to the root, and the Dockerfile is always sent
+        assert_eq!(
+            files().await,
+            vec![
+                ".dockerignore",
+                "Dockerfile",
+                "src/main.js",
+                "src/node_modules/local.js",
+                "tests/fixtures/keep.json"
+            ]
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
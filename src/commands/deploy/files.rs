@@ -0,0 +1,200 @@
+//! `hop deploy --dry-run`, lists what would be uploaded to the builder
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use tabwriter::TabWriter;
+use tokio::fs;
+
+use super::builder::util::{compress, find_files, slash_path};
+use crate::state::State;
+use crate::utils::size::user_friendly_size;
+
+/// Number of directories shown in the summary
+const LARGEST_DIRECTORIES: usize = 10;
+
+#[derive(Debug, Serialize)]
+struct ArchiveFile {
+    path: String,
+    size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveListing {
+    files: Vec<ArchiveFile>,
+    largest_directories: Vec<ArchiveFile>,
+    total_size: u64,
+    compressed_size: u64,
+    hash: String,
+}
+
+/// Files that would be uploaded from the directory with their sizes
+pub async fn archive_listing(dir: &Path) -> Result<ArchiveListing> {
+    let mut files = vec![];
+
+    for (path, full_path) in find_files(dir).await? {
+        let metadata = fs::metadata(&full_path).await?;
+
+        if metadata.is_dir() {
+            continue;
+        }
+
+        files.push(ArchiveFile {
+            path: slash_path(&path),
+            size: metadata.len(),
+        });
+    }
+
+    // pack it for real so the compressed size is exact
+    let archive = compress(
+        &format!("dry-run-{}", std::process::id()),
+        dir.to_path_buf(),
+    )
+    .await?;
+    let compressed_size = fs::metadata(&archive.path).await?.len();
+    fs::remove_file(&archive.path).await?;
+
+    Ok(ArchiveListing {
+        largest_directories: largest_directories(&files),
+        total_size: files.iter().map(|file| file.size).sum(),
+        compressed_size,
+        hash: archive.hash,
+        files,
+    })
+}
+
+/// Prints the listings of the services, or of the single directory keyed by `None`.
+/// Services are serialized as one document keyed by their name
+pub fn print_listings(
+    state: &State,
+    listings: &BTreeMap<Option<String>, ArchiveListing>,
+) -> Result<()> {
+    let output = match listings.get(&None) {
+        Some(listing) => state.output.serialize(listing)?,
+
+        None => state.output.serialize(
+            &listings
+                .iter()
+                .filter_map(|(name, listing)| Some((name.as_ref()?, listing)))
+                .collect::<BTreeMap<_, _>>(),
+        )?,
+    };
+
+    if let Some(output) = output {
+        println!("{output}");
+
+        return Ok(());
+    }
+
+    for (idx, (name, listing)) in listings.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+
+        if let Some(name) = name {
+            println!("{name}:");
+        }
+
+        print_listing(listing)?;
+    }
+
+    Ok(())
+}
+
+fn print_listing(listing: &ArchiveListing) -> Result<()> {
+    println!("{}", format_files(&listing.files)?.join("\n"));
+
+    if !listing.largest_directories.is_empty() {
+        println!("\nLargest directories:");
+        println!("{}", format_files(&listing.largest_directories)?.join("\n"));
+    }
+
+    println!(
+        "\n{} files, {} in total, {} compressed",
+        listing.files.len(),
+        user_friendly_size(listing.total_size)?,
+        user_friendly_size(listing.compressed_size)?
+    );
+
+    Ok(())
+}
+
+/// Directories with the size of every file under them, largest first
+fn largest_directories(files: &[ArchiveFile]) -> Vec<ArchiveFile> {
+    let mut sizes = HashMap::<&str, u64>::new();
+
+    for file in files {
+        let mut path = file.path.as_str();
+
+        while let Some((parent, _)) = path.rsplit_once('/') {
+            *sizes.entry(parent).or_default() += file.size;
+            path = parent;
+        }
+    }
+
+    let mut directories = sizes
+        .into_iter()
+        .map(|(path, size)| ArchiveFile {
+            path: format!("{path}/"),
+            size,
+        })
+        .collect::<Vec<_>>();
+
+    directories.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    directories.truncate(LARGEST_DIRECTORIES);
+
+    directories
+}
+
+fn format_files(files: &[ArchiveFile]) -> Result<Vec<String>> {
+    let mut tw = TabWriter::new(vec![]);
+
+    writeln!(&mut tw, "SIZE\tPATH")?;
+
+    for file in files {
+        writeln!(&mut tw, "{}\t{}", user_friendly_size(file.size)?, file.path)?;
+    }
+
+    Ok(String::from_utf8(tw.into_inner()?)?
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_largest_directories() {
+        let files = [
+            ("src/main.rs", 10),
+            ("src/lib/mod.rs", 30),
+            ("assets/logo.png", 25),
+            ("Dockerfile", 100),
+        ]
+        .into_iter()
+        .map(|(path, size)| ArchiveFile {
+            path: path.to_string(),
+            size,
+        })
+        .collect::<Vec<_>>();
+
+        let directories = largest_directories(&files)
+            .into_iter()
+            .map(|dir| (dir.path, dir.size))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            directories,
+            vec![
+                ("src/".to_string(), 40),
+                ("src/lib/".to_string(), 30),
+                ("assets/".to_string(), 25)
+            ]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 pub mod builder;
+mod files;
 pub mod local;
+mod services;
 
+use std::collections::BTreeMap;
 use std::env::current_dir;
 use std::path::PathBuf;
 
@@ -9,6 +12,7 @@ use clap::Parser;
 use leap_client_rs::leap::types::Event;
 use leap_client_rs::{LeapEdge, LeapOptions};
 
+use self::builder::BuildOptions;
 use crate::commands::auth::docker::HOP_REGISTRY_URL;
 use crate::commands::containers::types::{ContainerOptions, ContainerType};
 use crate::commands::containers::utils::create_containers;
@@ -21,27 +25,45 @@ use crate::commands::ignite::types::{
     ScalingStrategy,
 };
 use crate::commands::ignite::utils::{
-    create_deployment, env_file_to_map, get_deployment, rollout, update_deployment_config,
-    WEB_IGNITE_URL,
+    create_deployment, env_file_to_map, rollout, update_deployment_config, WEB_IGNITE_URL,
 };
+use crate::commands::projects::types::Project;
 use crate::commands::projects::utils::format_project;
 use crate::config::LEAP_PROJECT;
 use crate::state::State;
+use crate::store::config::DeployMode;
 use crate::store::hopfile::HopFile;
-use crate::utils::urlify;
+use crate::utils::{logging, prompt, urlify};
 
 const HOP_BUILD_BASE_URL: &str = "https://builder.hop.io/v1";
 
 #[derive(Debug, Parser)]
-#[clap(about = "Deploy a new container")]
+#[clap(
+    about = "Deploy a new container",
+    long_about = "Deploy a new container\n\nIn a monorepo whose hopfile lists `services`, pass the name of a service or `--all` to build and roll out the services in the order of their `depends_on`."
+)]
 #[group(skip)]
 pub struct Options {
     #[clap(
         name = "dir",
-        help = "Directory to deploy, defaults to current directory"
+        help = "Directory or service to deploy, defaults to current directory"
     )]
     path: Option<PathBuf>,
 
+    #[clap(
+        short,
+        long,
+        help = "Deploy every service of the monorepo",
+        conflicts_with = "dir"
+    )]
+    all: bool,
+
+    #[clap(
+        long,
+        help = "Deploy services that do not depend on each other at the same time"
+    )]
+    parallel: bool,
+
     #[clap(flatten)]
     config: DeploymentConfig,
 
@@ -62,14 +84,64 @@ pub struct Options {
     )]
     local: bool,
 
+    #[clap(
+        long,
+        help = "Build the container on the builder, overrides `deploy.mode` in the config",
+        conflicts_with = "local"
+    )]
+    remote: bool,
+
     #[clap(long, help = "Do not roll out the changes, only build")]
     no_rollout: bool,
+
+    #[clap(
+        long,
+        help = "Skip the build and rollout when the files did not change since the last deploy",
+        conflicts_with = "local"
+    )]
+    skip_unchanged: bool,
+
+    #[clap(
+        long,
+        visible_alias = "list-files",
+        help = "List the files that would be uploaded with their sizes, without deploying"
+    )]
+    dry_run: bool,
 }
 
-pub async fn handle(options: Options, state: State) -> Result<()> {
+pub async fn handle(mut options: Options, state: State) -> Result<()> {
     let mut dir = current_dir().context("Could not get current directory")?;
 
-    if let Some(path) = options.path {
+    options.local |= !options.remote && state.config.deploy.mode == Some(DeployMode::Local);
+
+    if options.all || options.path.is_some() {
+        let monorepo = HopFile::find_services(dir.clone()).await?;
+
+        if options.all {
+            let monorepo = monorepo.context(
+                "No hopfile with `services` found in the current directory or its parents",
+            )?;
+            let services = monorepo.services.keys().cloned().collect();
+
+            return services::deploy_services(&state, &monorepo, services, &options).await;
+        }
+
+        // a service name takes precedence over a directory with the same name
+        if let Some(monorepo) = monorepo {
+            if let Some(service) = options
+                .path
+                .as_ref()
+                .and_then(|path| path.to_str())
+                .filter(|name| monorepo.services.contains_key(*name))
+            {
+                let services = vec![service.to_string()];
+
+                return services::deploy_services(&state, &monorepo, services, &options).await;
+            }
+        }
+    }
+
+    if let Some(path) = &options.path {
         dir = dir
             .join(path)
             .canonicalize()
@@ -78,34 +150,41 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     ensure!(dir.is_dir(), "{} is not a directory", dir.display());
 
+    // the hopfile of a monorepo only lists its services, a service without
+    // its own hopfile gets one like any other directory
+    let hopfile = HopFile::find(dir.clone())
+        .await?
+        .filter(|hopfile| hopfile.deployment_ref().is_some() || hopfile.services.is_empty());
+
+    // the directory of the hopfile is deployed, even from one of its subdirectories
+    if let Some(hopfile) = &hopfile {
+        dir = hopfile
+            .path
+            .parent()
+            .context("Could not get the parent dir from the hop file location")?
+            .to_path_buf();
+    }
+
+    if options.dry_run {
+        let listing = files::archive_listing(&dir).await?;
+
+        return files::print_listings(&state, &BTreeMap::from([(None, listing)]));
+    }
+
     log::info!("Attempting to deploy {}", dir.display());
 
+    // only the flags decide whether the wizard runs, not the defaults from the config
     let is_visual = options.config == DeploymentConfig::default();
 
-    let (project, deployment, container_options, existing) = match HopFile::find(dir.clone()).await
-    {
-        Some(hopfile) => {
-            dir = hopfile
-                .path
-                .parent()
-                .context("Could not get the parent dir from the hop file location")?
-                .to_path_buf();
+    options.config.apply_defaults(&state.config.deploy);
 
+    let (project, deployment, container_options, existing) = match hopfile {
+        Some(hopfile) => {
             log::info!("Found hopfile: {}", hopfile.path.display());
 
             // TODO: possible update of deployment if it already exists?
-            let deployment = get_deployment(&state.http, &hopfile.config.deployment_id).await?;
-
-            // if deployment exists it's safe to unwrap
-            let project = state
-                .ctx
-                .find_project_by_id_or_namespace(&hopfile.config.project_id)
-                .with_context(|| {
-                    format!(
-                        "Could not find project with id {}",
-                        hopfile.config.project_id
-                    )
-                })?;
+            let deployment = state.get_hopfile_deployment(&hopfile).await?;
+            let project = state.get_hopfile_project(&hopfile)?;
 
             if is_visual {
                 log::warn!("Deployment exists, skipping arguments");
@@ -194,7 +273,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             // skip gateway creation if using default config
             if !options.yes
                 && !is_visual
-                && dialoguer::Confirm::new()
+                && prompt::Confirm::new("--yes")
                     .with_prompt("Do you want to create a Gateway? (You can always add one later)")
                     .interact()?
             {
@@ -217,14 +296,67 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 }
             }
 
-            HopFile::new(dir.clone().join("hop.yml"), &project.id, &deployment.id)
-                .save()
-                .await?;
+            HopFile::link(&dir, &project.id, &deployment.id).await?;
 
             (project, deployment, container_options, false)
         }
     };
 
+    logging::set_context("project", &project.id);
+    logging::set_context("deployment", &deployment.id);
+
+    let released = release(
+        &state,
+        &project,
+        &deployment,
+        dir,
+        &ReleaseOptions {
+            local: options.local,
+            no_rollout: options.no_rollout,
+            skip_unchanged: options.skip_unchanged,
+            existing,
+            containers: container_options.containers,
+            label: None,
+        },
+    )
+    .await?;
+
+    if !released {
+        return Ok(());
+    }
+
+    log::info!(
+        "Deployed successfully, you can find it at: {}",
+        urlify(&format!(
+            "{}/deployment/{}?project={}",
+            WEB_IGNITE_URL, deployment.id, project.namespace
+        ))
+    );
+
+    Ok(())
+}
+
+/// How a deployment is built and released
+struct ReleaseOptions {
+    local: bool,
+    no_rollout: bool,
+    skip_unchanged: bool,
+    /// Existing deployments are rolled out, new ones get their first containers
+    existing: bool,
+    containers: Option<u64>,
+    /// Prefix of the build logs when multiple deployments are built at once
+    label: Option<String>,
+}
+
+/// Builds the image of the deployment and rolls it out or starts its containers,
+/// returns `false` when nothing changed and the deployment was left as it is
+async fn release(
+    state: &State,
+    project: &Project,
+    deployment: &Deployment,
+    dir: PathBuf,
+    options: &ReleaseOptions,
+) -> Result<bool> {
     // connect to leap here so no logs interfere with the deploy
     let mut leap = LeapEdge::new(LeapOptions {
         token: Some(&state.ctx.current.clone().unwrap().leap_token),
@@ -237,19 +369,38 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     // all projects should already be subscribed but this is a precaution
     leap.channel_subscribe(&project.id).await?;
 
+    let mut pushed = None;
+
     if !options.local {
-        builder::build(&state, &project.id, &deployment.id, dir.clone(), &mut leap).await?;
+        pushed = builder::build(
+            state,
+            &project.id,
+            &deployment.id,
+            dir,
+            &mut leap,
+            &BuildOptions {
+                label: options.label.as_deref(),
+                skip_unchanged: options.skip_unchanged,
+            },
+        )
+        .await?;
+
+        if pushed.is_none() {
+            leap.close().await;
+
+            return Ok(false);
+        }
     } else {
         local::build(
-            &state,
+            state,
             &deployment.config.image.name,
-            dir.clone(),
+            dir,
             &deployment.config.env,
         )
         .await?;
     }
 
-    if existing {
+    if options.existing {
         if deployment.can_rollout() && !options.no_rollout {
             let rollout = rollout(&state.http, &deployment.id).await?;
 
@@ -259,7 +410,9 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                         continue;
                     }
 
-                    let Ok(rollout_event) = serde_json::from_value(serde_json::to_value(capsuled.data)?) else {
+                    let Ok(rollout_event) =
+                        serde_json::from_value(serde_json::to_value(capsuled.data)?)
+                    else {
                         continue;
                     };
 
@@ -270,6 +423,9 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                             }
                         }
 
+                        // other deployments of the project can be rolled out at the same time
+                        RolloutEvents::RolloutUpdate(event) if event.id != rollout.id => {}
+
                         RolloutEvents::RolloutUpdate(event) => match event.state {
                             // default state, when created
                             RolloutState::Pending => {}
@@ -288,7 +444,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 }
             }
         }
-    } else if let Some(containers) = container_options.containers {
+    } else if let Some(containers) = options.containers {
         if deployment.can_scale() && containers > 0 {
             create_containers(&state.http, &deployment.id, containers).await?;
         }
@@ -296,13 +452,10 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     leap.close().await;
 
-    log::info!(
-        "Deployed successfully, you can find it at: {}",
-        urlify(&format!(
-            "{}/deployment/{}?project={}",
-            WEB_IGNITE_URL, deployment.id, project.namespace
-        ))
-    );
+    // a build that was not rolled out is not skipped next time
+    if let Some(pushed) = pushed.filter(|_| !options.no_rollout) {
+        builder::record_build(&deployment.id, &pushed).await;
+    }
 
-    Ok(())
+    Ok(true)
 }
@@ -0,0 +1,364 @@
+//! Deploying the services of a monorepo, listed in the `services` of its hopfile
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Context, Result};
+use futures_util::future::join_all;
+use serde::Serialize;
+use tabwriter::TabWriter;
+use tokio::time::Instant;
+
+use super::files::{archive_listing, print_listings};
+use super::{release, Options, ReleaseOptions};
+use crate::commands::ignite::create::DeploymentConfig;
+use crate::commands::ignite::types::Deployment;
+use crate::commands::projects::types::Project;
+use crate::config::EXEC_NAME;
+use crate::state::State;
+use crate::store::hopfile::{HopFile, ServiceSpec};
+
+/// A service with the deployment it is deployed to
+struct Target {
+    dir: PathBuf,
+    project: Project,
+    deployment: Deployment,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Deployed,
+    /// Nothing changed since the last deploy, with `--skip-unchanged`
+    Unchanged,
+    Failed,
+    /// A service it depends on failed
+    Skipped,
+}
+
+impl Status {
+    fn is_success(self) -> bool {
+        matches!(self, Self::Deployed | Self::Unchanged)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ServiceResult {
+    service: String,
+    deployment_id: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    duration_secs: u64,
+}
+
+pub async fn deploy_services(
+    state: &State,
+    monorepo: &HopFile,
+    selected: Vec<String>,
+    options: &Options,
+) -> Result<()> {
+    let stages = deploy_stages(&monorepo.services, &selected)?;
+
+    log::info!(
+        "Deploying {} from {}",
+        stages
+            .iter()
+            .flatten()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        monorepo.path.display()
+    );
+
+    if options.dry_run {
+        let mut listings = BTreeMap::new();
+
+        for name in stages.iter().flatten() {
+            let dir = monorepo.service_dir(&monorepo.services[name]);
+
+            listings.insert(Some(name.clone()), archive_listing(&dir).await?);
+        }
+
+        return print_listings(state, &listings);
+    }
+
+    if options.config != DeploymentConfig::default() {
+        log::warn!("Services are deployed to existing deployments, skipping arguments");
+    }
+
+    // resolve every deployment first so a typo does not leave the services half deployed
+    let mut targets = HashMap::new();
+
+    for name in stages.iter().flatten() {
+        let service = &monorepo.services[name];
+        let dir = monorepo.service_dir(service);
+
+        ensure!(
+            dir.is_dir(),
+            "The directory of service `{name}` does not exist: {}",
+            dir.display()
+        );
+
+        let hopfile = HopFile::find(dir.clone())
+            .await?
+            .filter(|hopfile| hopfile.path.parent() == Some(dir.as_path()))
+            .with_context(|| {
+                format!(
+                    "Service `{name}` has no hopfile, run `{EXEC_NAME} deploy {}` to create its deployment",
+                    dir.display()
+                )
+            })?;
+
+        targets.insert(
+            name.clone(),
+            Target {
+                project: state.get_hopfile_project(&hopfile)?,
+                deployment: state.get_hopfile_deployment(&hopfile).await?,
+                dir,
+            },
+        );
+    }
+
+    let mut results: Vec<ServiceResult> = vec![];
+
+    for stage in stages {
+        let mut deploys = vec![];
+
+        for name in stage {
+            let target = &targets[&name];
+
+            let failed_dependency = monorepo.services[&name].depends_on.iter().find(|dep| {
+                results
+                    .iter()
+                    .any(|result| &result.service == *dep && !result.status.is_success())
+            });
+
+            if let Some(dependency) = failed_dependency {
+                log::warn!("Skipping `{name}` because `{dependency}` was not deployed");
+
+                results.push(ServiceResult {
+                    service: name,
+                    deployment_id: target.deployment.id.clone(),
+                    status: Status::Skipped,
+                    error: None,
+                    duration_secs: 0,
+                });
+
+                continue;
+            }
+
+            let deploy = deploy_service(state, name, target, options);
+
+            if options.parallel {
+                deploys.push(deploy);
+            } else {
+                results.push(deploy.await);
+            }
+        }
+
+        results.extend(join_all(deploys).await);
+    }
+
+    if let Some(output) = state.output.serialize(&results)? {
+        println!("{output}");
+    } else {
+        println!("{}", format_results(&results).join("\n"));
+    }
+
+    let deployed = results
+        .iter()
+        .filter(|result| result.status.is_success())
+        .count();
+
+    if deployed < results.len() {
+        bail!(
+            "{} of {} services were not deployed",
+            results.len() - deployed,
+            results.len()
+        );
+    }
+
+    log::info!("Deployed {deployed} services successfully");
+
+    Ok(())
+}
+
+async fn deploy_service(
+    state: &State,
+    name: String,
+    target: &Target,
+    options: &Options,
+) -> ServiceResult {
+    let started = Instant::now();
+
+    log::info!(
+        "Deploying `{name}` to deployment `{}`",
+        target.deployment.name
+    );
+
+    let result = release(
+        state,
+        &target.project,
+        &target.deployment,
+        target.dir.clone(),
+        &ReleaseOptions {
+            local: options.local,
+            no_rollout: options.no_rollout,
+            skip_unchanged: options.skip_unchanged,
+            existing: true,
+            containers: None,
+            label: options.parallel.then(|| name.clone()),
+        },
+    )
+    .await;
+
+    if let Err(error) = &result {
+        log::error!("Failed to deploy `{name}`: {error}");
+    }
+
+    ServiceResult {
+        deployment_id: target.deployment.id.clone(),
+        status: match &result {
+            Ok(true) => Status::Deployed,
+            Ok(false) => Status::Unchanged,
+            Err(_) => Status::Failed,
+        },
+        error: result.err().map(|error| error.to_string()),
+        duration_secs: started.elapsed().as_secs(),
+        service: name,
+    }
+}
+
+/// Groups the selected services so every group only depends on earlier ones,
+/// the services of a group can be deployed at the same time
+fn deploy_stages(
+    services: &BTreeMap<String, ServiceSpec>,
+    selected: &[String],
+) -> Result<Vec<Vec<String>>> {
+    for name in selected {
+        ensure!(services.contains_key(name), "Unknown service `{name}`");
+    }
+
+    for (name, service) in services {
+        for dependency in &service.depends_on {
+            ensure!(
+                services.contains_key(dependency),
+                "Service `{name}` depends on `{dependency}` which is not in the hopfile"
+            );
+        }
+    }
+
+    let selected = selected.iter().collect::<HashSet<_>>();
+    let mut remaining = services.iter().collect::<BTreeMap<_, _>>();
+    let mut stages = vec![];
+
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .filter(|(_, service)| {
+                service
+                    .depends_on
+                    .iter()
+                    .all(|dependency| !remaining.contains_key(dependency))
+            })
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+
+        ensure!(
+            !ready.is_empty(),
+            "The dependencies of {} form a cycle",
+            remaining
+                .keys()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        for name in &ready {
+            remaining.remove(name);
+        }
+
+        // services that are not selected still order the ones that are
+        let stage = ready
+            .into_iter()
+            .filter(|name| selected.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !stage.is_empty() {
+            stages.push(stage);
+        }
+    }
+
+    Ok(stages)
+}
+
+fn format_results(results: &[ServiceResult]) -> Vec<String> {
+    let mut tw = TabWriter::new(vec![]);
+
+    writeln!(&mut tw, "SERVICE\tDEPLOYMENT\tSTATUS\tDURATION").unwrap();
+
+    for result in results {
+        writeln!(
+            &mut tw,
+            "{}\t{}\t{}\t{}s",
+            result.service,
+            result.deployment_id,
+            serde_json::to_string(&result.status)
+                .unwrap()
+                .replace('"', ""),
+            result.duration_secs
+        )
+        .unwrap();
+    }
+
+    String::from_utf8(tw.into_inner().unwrap())
+        .unwrap()
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn service(depends_on: &[&str]) -> ServiceSpec {
+        ServiceSpec {
+            path: PathBuf::from("."),
+            depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_deploy_stages() {
+        let services = BTreeMap::from([
+            ("web".to_string(), service(&["api"])),
+            ("api".to_string(), service(&["db", "cache"])),
+            ("db".to_string(), service(&[])),
+            ("cache".to_string(), service(&[])),
+            ("docs".to_string(), service(&[])),
+        ]);
+
+        let all = services.keys().cloned().collect::<Vec<_>>();
+
+        assert_eq!(
+            deploy_stages(&services, &all).unwrap(),
+            vec![vec!["cache", "db", "docs"], vec!["api"], vec!["web"]]
+        );
+        assert_eq!(
+            deploy_stages(&services, &["web".to_string(), "db".to_string()]).unwrap(),
+            vec![vec!["db"], vec!["web"]]
+        );
+        assert!(deploy_stages(&services, &["worker".to_string()]).is_err());
+
+        let cycle = BTreeMap::from([
+            ("a".to_string(), service(&["b"])),
+            ("b".to_string(), service(&["a"])),
+        ]);
+
+        assert!(deploy_stages(&cycle, &["a".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use std::env::current_dir;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::commands::apply::utils::{plan, plan_deployment_update};
+use crate::commands::ignite::create::Options as CreateOptions;
+use crate::commands::ignite::utils::update_deployment_config;
+use crate::config::EXEC_NAME;
+use crate::state::State;
+use crate::store::hopfile::{HopFile, LATEST_VERSION};
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Show how a deployment differs from the hopfile or the passed flags",
+    long_about = "Show how a deployment differs from the hopfile or the passed flags\n\nWithout flags the deployment, its Gateways and health checks are compared with the `deployment` section of the hopfile. With the flags of `ignite update` only the deployment is compared with them."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        name = "dir",
+        help = "Directory with the hopfile, defaults to current directory"
+    )]
+    pub path: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Name or ID of the deployment to compare with the flags, defaults to the one in the hopfile"
+    )]
+    pub deployment: Option<String>,
+
+    #[clap(flatten)]
+    pub config: CreateOptions,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    let mut dir = current_dir()?;
+
+    if let Some(path) = &options.path {
+        dir = dir.join(path).canonicalize()?;
+    }
+
+    let hopfile = HopFile::find(dir.clone()).await?;
+
+    let (plan, command) = if options.config == CreateOptions::default() {
+        if options.deployment.is_some() {
+            bail!("Pass the flags to compare `--deployment` with, e.g. `--ram 1G`");
+        }
+
+        let hopfile = hopfile.with_context(|| format!("No hopfile found in {}", dir.display()))?;
+
+        let Some(spec) = &hopfile.deployment else {
+            bail!(
+                "{} does not describe a deployment, add a `deployment` section and set `version: {LATEST_VERSION}` to compare it",
+                hopfile.path.display()
+            );
+        };
+
+        let project = state.get_hopfile_project(&hopfile)?;
+        let deployment = state.find_hopfile_deployment(&hopfile).await?;
+
+        (
+            plan(&state, &project, spec, deployment.as_ref()).await?,
+            "apply",
+        )
+    } else {
+        let (project, deployment) = match (&options.deployment, &hopfile) {
+            (Some(name_or_id), _) => (
+                state.ctx.current_project_error()?,
+                state.get_deployment_by_name_or_id(name_or_id).await?,
+            ),
+
+            (None, Some(hopfile)) => (
+                state.get_hopfile_project(hopfile)?,
+                state.get_hopfile_deployment(hopfile).await?,
+            ),
+
+            (None, None) => bail!(
+                "No hopfile found, pass `--deployment` to choose the deployment to compare with"
+            ),
+        };
+
+        let (config, container_options) = update_deployment_config(
+            &state.http,
+            options.config,
+            false,
+            &deployment,
+            &None,
+            true,
+            &project,
+        )
+        .await?;
+
+        let plan = plan_deployment_update(
+            &state,
+            &project,
+            &deployment,
+            config,
+            container_options.containers,
+        )
+        .await?;
+
+        (plan, "ignite update")
+    };
+
+    if plan.is_empty() && plan.missing_secrets.is_empty() {
+        log::info!("No differences");
+
+        return Ok(());
+    }
+
+    println!("{}", plan.format()?.join("\n"));
+
+    if !plan.is_empty() {
+        log::info!("Run `{EXEC_NAME} {command}` to make these changes");
+    }
+
+    Ok(())
+}
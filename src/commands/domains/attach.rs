@@ -5,12 +5,13 @@ use super::util::attach_domain;
 use crate::commands::gateways::util::{format_gateways, get_all_gateways};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Attach a domain to a Gateway")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the Gateway")]
+    #[clap(help = "Name, ID or `<deployment>/<index>` of the Gateway")]
     pub gateway: Option<String>,
 
     #[clap(help = "Name of the domain")]
@@ -19,7 +20,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let gateway_id = match options.gateway {
-        Some(id) => id,
+        Some(reference) => state.get_gateway_by_name_or_id(&reference).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -28,7 +29,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -38,7 +39,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!gateways.is_empty(), "No Gateways found");
             let gateways_fmt = format_gateways(&gateways, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a Gateway")
                 .default(0)
                 .items(&gateways_fmt)
@@ -51,7 +52,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let domain = match options.domain {
         Some(name) => name,
 
-        None => dialoguer::Input::<String>::new()
+        None => prompt::Input::<String>::new("<DOMAIN>")
             .with_prompt("Enter the domain name")
             .interact()?,
     };
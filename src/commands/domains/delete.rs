@@ -5,18 +5,19 @@ use super::util::{delete_domain, format_domains, get_all_domains};
 use crate::commands::gateways::util::{format_gateways, get_all_gateways};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Detach a domain from a Gateway")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the domain")]
+    #[clap(help = "Domain or ID of the domain")]
     pub domain: Option<String>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let domain_id = match options.domain {
-        Some(id) => id,
+        Some(reference) => state.get_domain_by_name_or_id(&reference).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -25,7 +26,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DOMAIN>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -35,7 +36,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!gateways.is_empty(), "No Gateways found");
             let gateways_fmt = format_gateways(&gateways, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DOMAIN>")
                 .with_prompt("Select a Gateway")
                 .default(0)
                 .items(&gateways_fmt)
@@ -44,7 +45,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             let domains = get_all_domains(&state.http, &gateways[idx].id).await?;
             let domains_fmt = format_domains(&domains, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DOMAIN>")
                 .with_prompt("Select a domain")
                 .default(0)
                 .items(&domains_fmt)
@@ -5,12 +5,13 @@ use super::util::{format_domains, get_all_domains};
 use crate::commands::gateways::util::{format_gateways, get_all_gateways};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "List all domains attached to a Gateway")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the Gateway")]
+    #[clap(help = "Name, ID or `<deployment>/<index>` of the Gateway")]
     pub gateway: Option<String>,
 
     #[clap(short, long, help = "Only display domain IDs")]
@@ -19,7 +20,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let gateway_id = match options.gateway {
-        Some(id) => id,
+        Some(reference) => state.get_gateway_by_name_or_id(&reference).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -28,7 +29,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -38,7 +39,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!gateways.is_empty(), "No Gateways found");
             let gateways_fmt = format_gateways(&gateways, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a Gateway")
                 .default(0)
                 .items(&gateways_fmt)
@@ -50,7 +51,9 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     let domains = get_all_domains(&state.http, &gateway_id).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&domains)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = domains
             .iter()
             .map(|d| d.id.as_str())
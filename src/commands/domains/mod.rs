@@ -2,7 +2,7 @@ mod attach;
 mod delete;
 mod list;
 pub mod types;
-mod util;
+pub mod util;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -2,12 +2,14 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
+use crate::utils::resolve::Resolvable;
+
 #[derive(Serialize)]
 pub struct AttachDomain<'a> {
     pub domain: &'a str,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Domain {
     pub id: String,
     pub domain: String,
@@ -15,6 +17,18 @@ pub struct Domain {
     pub state: DomainState,
 }
 
+impl Resolvable for Domain {
+    const KIND: &'static str = "domain";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.domain)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum DomainState {
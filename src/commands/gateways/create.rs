@@ -6,7 +6,7 @@ use crate::commands::gateways::types::GatewayConfig;
 use crate::commands::gateways::util::{create_gateway, update_gateway_config};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
-use crate::utils::urlify;
+use crate::utils::{prompt, urlify};
 
 #[derive(Debug, Parser, Default, PartialEq, Eq)]
 pub struct GatewayOptions {
@@ -30,7 +30,7 @@ pub struct GatewayOptions {
 #[clap(about = "Create a Gateway")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "deployment", help = "ID of the deployment")]
+    #[clap(name = "deployment", help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(flatten)]
@@ -39,7 +39,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(deployment) => deployment,
+        Some(deployment) => state.get_deployment_by_name_or_id(&deployment).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -48,7 +48,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -3,13 +3,18 @@ use clap::Parser;
 
 use crate::commands::gateways::util::{delete_gateway, format_gateways, get_all_gateways};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete gateways")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "gateways", help = "IDs of the gateways")]
+    #[clap(
+        name = "gateways",
+        help = "Names, IDs or `<deployment>/<index>` of the gateways"
+    )]
     gateways: Vec<String>,
 
     #[clap(short = 'f', long = "force", help = "Skip confirmation")]
@@ -18,7 +23,13 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let gateways = if !options.gateways.is_empty() {
-        options.gateways
+        let mut ids = vec![];
+
+        for reference in &options.gateways {
+            ids.push(state.get_gateway_by_name_or_id(reference).await?.id);
+        }
+
+        ids
     } else {
         let project_id = state.ctx.current_project_error()?.id;
 
@@ -26,7 +37,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<GATEWAYS>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -35,7 +46,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         let gateways = get_all_gateways(&state.http, &deployments[idx].id).await?;
         let gateways_fmt = format_gateways(&gateways, false);
 
-        let idxs = dialoguer::MultiSelect::new()
+        let idxs = prompt::MultiSelect::new("<GATEWAYS>")
             .with_prompt("Select Gateways to delete")
             .items(&gateways_fmt)
             .interact()?;
@@ -49,7 +60,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete {} Gateways?",
                 gateways.len()
@@ -57,7 +68,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     let mut delete_count = 0;
@@ -1,15 +1,18 @@
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use crate::commands::gateways::util::{format_gateways, get_all_gateways};
+use crate::commands::gateways::util::{format_gateways, get_gateways};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::watch::{self, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "List all Gateways")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "deployment", help = "ID of the deployment")]
+    #[clap(name = "deployment", help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(
@@ -18,11 +21,17 @@ pub struct Options {
         help = "Only print the IDs of the deployments"
     )]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub limit: LimitOptions,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(deployment) => deployment,
+        Some(deployment) => state.get_deployment_by_name_or_id(&deployment).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -31,7 +40,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -41,21 +50,25 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let gateways = get_all_gateways(&state.http, &deployment_id).await?;
+    let (http, output, deployment_id) = (&state.http, state.output, &deployment_id);
 
-    if options.quiet {
-        let ids = gateways
-            .iter()
-            .map(|d| d.id.as_str())
-            .collect::<Vec<_>>()
-            .join(" ");
+    // there are no Leap events for Gateways so they are only refreshed on an interval
+    watch::render(&state, options.watch, &[], || async move {
+        let gateways = get_gateways(http, deployment_id, options.limit.into()).await?;
 
-        println!("{ids}");
-    } else {
-        let containers_fmt = format_gateways(&gateways, true);
+        if let Some(output) = output.serialize(&gateways)? {
+            Ok(vec![output])
+        } else if options.quiet {
+            let ids = gateways
+                .iter()
+                .map(|d| d.id.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
 
-        println!("{}", containers_fmt.join("\n"));
-    }
-
-    Ok(())
+            Ok(vec![ids])
+        } else {
+            Ok(format_gateways(&gateways, true))
+        }
+    })
+    .await
 }
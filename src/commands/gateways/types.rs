@@ -2,11 +2,15 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::domains::types::Domain;
+use crate::impl_paginated;
+use crate::utils::resolve::Resolvable;
 
-#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
 pub struct GatewayConfig {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub type_: Option<GatewayType>,
@@ -32,7 +36,7 @@ impl GatewayConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct Gateway {
     pub id: String,
     pub created_at: String,
@@ -47,6 +51,18 @@ pub struct Gateway {
     pub domains: Vec<Domain>,
 }
 
+impl Resolvable for Gateway {
+    const KIND: &'static str = "gateway";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
 impl Gateway {
     pub fn full_url(&self) -> String {
         match self.type_ {
@@ -74,7 +90,9 @@ pub struct MultipleGateways {
     pub gateways: Vec<Gateway>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+impl_paginated!(MultipleGateways => gateways: Gateway);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum GatewayType {
     #[default]
@@ -106,7 +124,7 @@ impl GatewayType {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum GatewayProtocol {
     #[default]
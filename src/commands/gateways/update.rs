@@ -4,16 +4,20 @@ use clap::Parser;
 use super::create::GatewayOptions;
 use crate::commands::gateways::types::GatewayConfig;
 use crate::commands::gateways::util::{
-    format_gateways, get_all_gateways, get_gateway, update_gateway, update_gateway_config,
+    format_gateways, get_all_gateways, update_gateway, update_gateway_config,
 };
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Update a Gateway")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "gateway", help = "ID of the Gateway")]
+    #[clap(
+        name = "gateway",
+        help = "Name, ID or `<deployment>/<index>` of the Gateway"
+    )]
     pub gateway: Option<String>,
 
     #[clap(flatten)]
@@ -22,7 +26,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let gateway = match options.gateway {
-        Some(gateway_id) => get_gateway(&state.http, &gateway_id).await?,
+        Some(reference) => state.get_gateway_by_name_or_id(&reference).await?,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -31,7 +35,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -40,7 +44,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             let gateways = get_all_gateways(&state.http, &deployments[idx].id).await?;
             let gateways_fmt = format_gateways(&gateways, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<GATEWAY>")
                 .with_prompt("Select a Gateway to update")
                 .default(0)
                 .items(&gateways_fmt)
@@ -8,8 +8,9 @@ use super::create::GatewayOptions;
 use super::types::{
     Gateway, GatewayConfig, GatewayProtocol, GatewayType, MultipleGateways, SingleGateway,
 };
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
-use crate::utils::ask_question_iter;
+use crate::utils::{ask_question_iter, prompt};
 
 pub async fn create_gateway(
     http: &HttpClient,
@@ -32,16 +33,20 @@ pub async fn create_gateway(
 }
 
 pub async fn get_all_gateways(http: &HttpClient, deployment_id: &str) -> Result<Vec<Gateway>> {
-    let response = http
-        .request::<MultipleGateways>(
-            "GET",
-            &format!("/ignite/deployments/{deployment_id}/gateways"),
-            None,
-        )
-        .await?
-        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+    get_gateways(http, deployment_id, PageOptions::default()).await
+}
 
-    Ok(response.gateways)
+pub async fn get_gateways(
+    http: &HttpClient,
+    deployment_id: &str,
+    page: PageOptions,
+) -> Result<Vec<Gateway>> {
+    http.request_all::<MultipleGateways>(
+        &format!("/ignite/deployments/{deployment_id}/gateways"),
+        PageStyle::Unpaginated,
+        page,
+    )
+    .await
 }
 
 pub async fn get_gateway(http: &HttpClient, gateway_id: &str) -> Result<Gateway> {
@@ -175,7 +180,7 @@ fn update_config_from_guided(gateway_config: &mut GatewayConfig, is_update: bool
     let name = gateway_config.name.clone().unwrap_or_default();
 
     gateway_config.name = Some(
-        dialoguer::Input::<String>::new()
+        prompt::Input::<String>::new("--name")
             .with_prompt("Gateway name (optional)")
             .show_default(name.is_empty())
             .default(name)
@@ -187,7 +192,7 @@ fn update_config_from_guided(gateway_config: &mut GatewayConfig, is_update: bool
     }
 
     let gateway_type = if !is_update {
-        let value = ask_question_iter("Gateway type", &GatewayType::values(), None)?;
+        let value = ask_question_iter("--type", "Gateway type", &GatewayType::values(), None)?;
 
         gateway_config.type_ = Some(value.clone());
 
@@ -210,7 +215,7 @@ fn update_config_from_guided(gateway_config: &mut GatewayConfig, is_update: bool
             let internal_domain_regex = Regex::new(r"(?i)^[a-z0-9-.]+.hop$").unwrap();
 
             gateway_config.internal_domain = Some(
-                dialoguer::Input::<String>::new()
+                prompt::Input::<String>::new("--internal-domain")
                     .with_prompt("Internal domain")
                     .show_default(!internal_domain.is_empty())
                     .default(internal_domain)
@@ -245,13 +250,14 @@ fn update_config_from_guided(gateway_config: &mut GatewayConfig, is_update: bool
             gateway_config.internal_domain = None;
 
             gateway_config.protocol = Some(ask_question_iter(
+                "--protocol",
                 "Protocol",
                 &GatewayProtocol::values(),
                 gateway_config.protocol.clone(),
             )?);
 
             gateway_config.target_port = Some(
-                dialoguer::Input::<u16>::new()
+                prompt::Input::<u16>::new("--target-port")
                     .with_prompt("Target port")
                     .default(gateway_config.target_port.unwrap_or(8080))
                     .show_default(gateway_config.target_port.is_some())
@@ -4,13 +4,15 @@ use clap::Parser;
 use super::utils::{cancel_build, format_builds, get_all_builds};
 use crate::commands::ignite::builds::types::BuildState;
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Cancel a running build")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "ID or `<deployment>/<index>` of the build")]
     pub build: Option<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -19,7 +21,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let build_id = match options.build {
-        Some(id) => id,
+        Some(reference) => state.get_build_by_name_or_id(&reference).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -28,7 +30,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<BUILD>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -42,7 +44,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!builds.is_empty(), "No running builds found");
             let builds_fmt = format_builds(&builds, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<BUILD>")
                 .with_prompt("Select a build")
                 .items(&builds_fmt)
                 .default(0)
@@ -53,12 +55,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt("Are you sure you want to cancel this build?")
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted by user");
+        bail!(HopError::cancelled());
     }
 
     cancel_build(&state.http, &build_id).await?;
@@ -1,24 +1,33 @@
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use super::utils::{format_builds, get_all_builds};
+use super::utils::{format_builds, get_builds};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::watch::{self, WatchEvent, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "List all builds in a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(short, long, help = "Only print the IDs of the builds")]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub limit: LimitOptions,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -27,7 +36,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -37,21 +46,29 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let builds = get_all_builds(&state.http, &deployment_id).await?;
-
-    if options.quiet {
-        let ids = builds
-            .iter()
-            .map(|d| d.id.as_str())
-            .collect::<Vec<_>>()
-            .join(" ");
+    let (http, output, deployment_id) = (&state.http, state.output, &deployment_id);
 
-        println!("{ids}");
-    } else {
-        let builds_fmt = format_builds(&builds, true);
+    watch::render(
+        &state,
+        options.watch,
+        &[WatchEvent::Builds],
+        || async move {
+            let builds = get_builds(http, deployment_id, options.limit.into()).await?;
 
-        println!("{}", builds_fmt.join("\n"));
-    }
+            if let Some(output) = output.serialize(&builds)? {
+                Ok(vec![output])
+            } else if options.quiet {
+                let ids = builds
+                    .iter()
+                    .map(|d| d.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
 
-    Ok(())
+                Ok(vec![ids])
+            } else {
+                Ok(format_builds(&builds, true))
+            }
+        },
+    )
+    .await
 }
@@ -3,12 +3,17 @@ use std::fmt::Display;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::impl_paginated;
+use crate::utils::resolve::Resolvable;
+
 #[derive(Debug, Deserialize)]
 pub struct MultipleBuilds {
     pub builds: Vec<Build>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl_paginated!(MultipleBuilds => builds: Build);
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum BuildMethod {
     Cli,
@@ -25,7 +30,7 @@ impl Display for BuildMethod {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum BuildState {
     Pending,
@@ -44,7 +49,7 @@ impl Display for BuildState {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Build {
     pub id: String,
     pub deployment_id: String,
@@ -54,3 +59,11 @@ pub struct Build {
     pub digest: Option<String>,
     pub finished_at: Option<DateTime<Utc>>,
 }
+
+impl Resolvable for Build {
+    const KIND: &'static str = "build";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -5,24 +5,30 @@ use ms::{__to_string__, ms};
 use serde_json::Value;
 
 use super::types::{Build, MultipleBuilds};
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
 use crate::utils::relative_time;
 
 pub async fn get_all_builds(http: &HttpClient, deployment_id: &str) -> Result<Vec<Build>> {
-    let mut response = http
-        .request::<MultipleBuilds>(
-            "GET",
+    get_builds(http, deployment_id, PageOptions::default()).await
+}
+
+pub async fn get_builds(
+    http: &HttpClient,
+    deployment_id: &str,
+    page: PageOptions,
+) -> Result<Vec<Build>> {
+    let mut builds = http
+        .request_all::<MultipleBuilds>(
             &format!("/ignite/deployments/{deployment_id}/builds"),
-            None,
+            PageStyle::Unpaginated,
+            page,
         )
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Could not parse response"))?;
+        .await?;
 
-    response
-        .builds
-        .sort_by_cached_key(|build| std::cmp::Reverse(build.started_at.timestamp()));
+    builds.sort_by_cached_key(|build| std::cmp::Reverse(build.started_at.timestamp()));
 
-    Ok(response.builds)
+    Ok(builds)
 }
 
 pub async fn cancel_build(http: &HttpClient, build_id: &str) -> Result<()> {
@@ -6,6 +6,7 @@ use crate::commands::containers::utils::create_containers;
 use crate::commands::ignite::types::Deployment;
 use crate::commands::ignite::utils::{create_deployment, update_deployment_config, WEB_IGNITE_URL};
 use crate::state::State;
+use crate::store::config::DeployConfig;
 use crate::utils::urlify;
 
 #[derive(Debug, Parser, Default, PartialEq, Clone)]
@@ -63,6 +64,17 @@ pub struct DeploymentConfig {
     pub rm: bool,
 }
 
+impl DeploymentConfig {
+    /// Uses the configured resources when none were passed
+    pub fn apply_defaults(&mut self, defaults: &DeployConfig) {
+        if self.tier.is_none() && self.cpu.is_none() && self.ram.is_none() {
+            self.tier.clone_from(&defaults.tier);
+            self.cpu = defaults.cpu;
+            self.ram.clone_from(&defaults.ram);
+        }
+    }
+}
+
 #[derive(Debug, Parser, Default, PartialEq, Eq, Clone)]
 pub struct VolumeConfig {
     #[clap(short, long, help = "Volume mount to use")]
@@ -86,7 +98,7 @@ pub struct Options {
     pub image: Option<String>,
 }
 
-pub async fn handle(options: Options, state: State) -> Result<()> {
+pub async fn handle(mut options: Options, state: State) -> Result<()> {
     let project = state.ctx.current_project_error()?;
 
     log::info!(
@@ -98,6 +110,8 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     let is_visual = options == Options::default();
 
+    options.config.apply_defaults(&state.config.deploy);
+
     let (deployment_config, container_options) = update_deployment_config(
         &state.http,
         options,
@@ -2,13 +2,15 @@ use anyhow::{bail, ensure, Result};
 use clap::Parser;
 
 use crate::commands::ignite::utils::{delete_deployment, format_deployments, get_all_deployments};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment to delete")]
+    #[clap(help = "Name or ID of the deployment to delete")]
     deployment: Option<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -17,7 +19,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -26,7 +28,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -37,12 +39,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt("Are you sure you want to delete the deployment?")
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     delete_deployment(&state.http, &deployment_id).await?;
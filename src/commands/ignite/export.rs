@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use tokio::fs;
+
+use crate::commands::domains::util::get_all_domains;
+use crate::commands::gateways::util::get_all_gateways;
+use crate::commands::ignite::health::types::CreateHealthCheck;
+use crate::commands::ignite::health::utils::get_all_health_checks;
+use crate::commands::ignite::types::{CreateDeployment, Deployment};
+use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::commands::secrets::utils::get_secret_name;
+use crate::state::State;
+use crate::store::hopfile::{DeploymentSpec, GatewaySpec, HopFile, HopFileConfig, LATEST_VERSION};
+use crate::utils::output::OutputFormat;
+use crate::utils::prompt;
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Export deployments to hopfiles",
+    long_about = "Export deployments to hopfiles\n\nThe hopfiles describe the deployment with its Gateways, domains and health checks, and can be applied again with `hop apply`. Secrets used in env are kept as `${secrets.NAME}` references, the format the API stores them in, so `hop apply` sends them back unchanged and variable interpolation leaves them alone. Other `$` in env values are written as `$$`."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(help = "Name or ID of the deployment")]
+    pub deployment: Option<String>,
+
+    #[clap(short, long, help = "Export every deployment of the project")]
+    pub all: bool,
+
+    #[clap(
+        long,
+        help = "Write every deployment to `<DIR>/<name>/hop.yml` instead of printing them"
+    )]
+    pub out_dir: Option<PathBuf>,
+}
+
+pub async fn handle(options: Options, state: State) -> Result<()> {
+    let project = state.ctx.current_project_error()?;
+
+    let deployments = match options.deployment {
+        Some(_) if options.all => bail!("Pass either a deployment or `--all`"),
+
+        Some(name_or_id) => vec![state.get_deployment_by_name_or_id(&name_or_id).await?],
+
+        None => {
+            let deployments = get_all_deployments(&state.http, &project.id).await?;
+            ensure!(!deployments.is_empty(), "No deployments found");
+
+            if options.all {
+                deployments
+            } else {
+                let deployments_fmt = format_deployments(&deployments, false);
+
+                let idx = prompt::Select::new("<DEPLOYMENT>")
+                    .with_prompt("Select a deployment")
+                    .items(&deployments_fmt)
+                    .default(0)
+                    .interact()?;
+
+                vec![deployments[idx].clone()]
+            }
+        }
+    };
+
+    let mut hopfiles = vec![];
+
+    for deployment in deployments {
+        let path = options
+            .out_dir
+            .as_ref()
+            .map(|dir| dir.join(&deployment.name).join("hop.yml"))
+            .unwrap_or_default();
+
+        hopfiles.push(export_deployment(&state, &project.id, deployment, path).await?);
+    }
+
+    if options.out_dir.is_some() {
+        for hopfile in hopfiles {
+            if let Some(parent) = hopfile.path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+
+            hopfile.save().await?;
+        }
+
+        return Ok(());
+    }
+
+    let output = match state.output {
+        OutputFormat::Json if options.all => serde_json::to_string_pretty(&hopfiles)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&hopfiles[0])?,
+
+        // one YAML document per deployment
+        _ => hopfiles
+            .iter()
+            .map(serde_yaml::to_string)
+            .collect::<Result<Vec<_>, _>>()?
+            .join("---\n"),
+    };
+
+    println!("{}", output.trim_end());
+
+    Ok(())
+}
+
+/// Hopfile describing the deployment as it is now
+pub async fn export_deployment(
+    state: &State,
+    project_id: &str,
+    deployment: Deployment,
+    path: PathBuf,
+) -> Result<HopFile> {
+    let (gateways, health_checks) = tokio::join!(
+        get_all_gateways(&state.http, &deployment.id),
+        get_all_health_checks(&state.http, &deployment.id)
+    );
+
+    let mut gateway_specs = vec![];
+
+    for mut gateway in gateways? {
+        gateway.domains = get_all_domains(&state.http, &gateway.id).await?;
+
+        gateway_specs.push(GatewaySpec::from_gateway(&gateway));
+    }
+
+    let containers = deployment
+        .can_scale()
+        .then_some(deployment.target_container_count);
+
+    let id = deployment.id.clone();
+    let mut config = CreateDeployment::from(deployment);
+
+    // secrets are referenced by name, normalize them to how `hop secrets` shows them,
+    // other values are escaped so they are not taken for variables of the hopfile
+    for value in config.env.values_mut() {
+        *value = match get_secret_name(value) {
+            Some(name) => format!("${{secrets.{}}}", name.to_uppercase()),
+            None => value.replace('$', "$$"),
+        };
+    }
+
+    Ok(HopFile {
+        schema: None,
+        version: LATEST_VERSION,
+        config: HopFileConfig {
+            project_id: project_id.to_string(),
+            deployment_id: id,
+        },
+        deployment: Some(DeploymentSpec {
+            config,
+            containers,
+            gateways: gateway_specs,
+            health_checks: health_checks?.iter().map(CreateHealthCheck::from).collect(),
+            ..Default::default()
+        }),
+        services: BTreeMap::new(),
+        environments: BTreeMap::new(),
+        environment: None,
+        path,
+    })
+}
@@ -13,6 +13,7 @@ use tokio::fs;
 use self::types::DockerCompose;
 use self::utils::order_by_dependencies;
 use crate::commands::auth::docker::HOP_REGISTRY_URL;
+use crate::commands::deploy::builder::BuildOptions;
 use crate::commands::deploy::{builder, local};
 use crate::commands::gateways::types::GatewayConfig;
 use crate::commands::gateways::util::{create_gateway, update_gateway_config};
@@ -27,7 +28,7 @@ use crate::commands::ignite::utils::{
 use crate::config::LEAP_PROJECT;
 use crate::state::State;
 use crate::store::hopfile::HopFile;
-use crate::utils::urlify;
+use crate::utils::{prompt, urlify};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Creates new Ignite deployments from a Docker compose file")]
@@ -35,6 +36,11 @@ use crate::utils::urlify;
 pub struct Options {
     #[clap(help = "The file to read from. Defaults to docker-compose.yml")]
     pub file: Option<PathBuf>,
+    #[clap(
+        long = "build-locally",
+        help = "Build the services that require building locally instead of on our build servers"
+    )]
+    pub build_locally: Option<bool>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
@@ -213,10 +219,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         .iter()
         .any(|(_, _, build, _, _)| build.is_some());
 
-    let build_localy = if has_unbuilt {
+    let build_localy = if let Some(build_locally) = options.build_locally {
+        has_unbuilt && build_locally
+    } else if has_unbuilt {
         log::info!("Some of the services in the compose file require building. They can be built locally or on our build servers");
 
-        let answer = dialoguer::Confirm::new()
+        let answer = prompt::Confirm::new("--build-locally")
             .with_prompt("Would you like to build them locally?")
             .default(true)
             .interact()?;
@@ -268,7 +276,15 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             if build_localy {
                 local::build(&state, &dep.config.image.name, path, &dep.config.env).await?;
             } else {
-                builder::build(&state, &project.id, &dep.id, path, &mut leap).await?;
+                builder::build(
+                    &state,
+                    &project.id,
+                    &dep.id,
+                    path,
+                    &mut leap,
+                    &BuildOptions::default(),
+                )
+                .await?;
             }
         }
 
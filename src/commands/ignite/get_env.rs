@@ -1,23 +1,25 @@
+use std::collections::BTreeMap;
 use std::io::Write;
 
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
+use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::commands::secrets::utils::get_secret_name;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Get current deployments env values")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment to get env values")]
+    #[clap(help = "Name or ID of the deployment to get env values")]
     pub deployment: Option<String>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment = match options.deployment {
-        Some(id) => get_deployment(&state.http, &id).await?,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -26,7 +28,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -36,15 +38,30 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let mut buff = vec![];
+    let env = deployment
+        .config
+        .env
+        .into_iter()
+        .map(|(key, value)| {
+            let value = if let Some(secret_name) = get_secret_name(&value) {
+                format!("{{{secret_name}}}")
+            } else {
+                value
+            };
+
+            (key, value)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    if let Some(output) = state.output.serialize(&env)? {
+        println!("{output}");
 
-    for (key, value) in deployment.config.env {
-        let value = if let Some(secret_name) = get_secret_name(&value) {
-            format!("{{{secret_name}}}")
-        } else {
-            value
-        };
+        return Ok(());
+    }
+
+    let mut buff = vec![];
 
+    for (key, value) in env {
         writeln!(buff, "{key}={value}")?;
     }
 
@@ -4,12 +4,13 @@ use clap::Parser;
 use super::utils::{create_health_check, create_health_check_config};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Create Health Checks for a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "deployment", help = "ID of the deployment")]
+    #[clap(name = "deployment", help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(flatten)]
@@ -39,7 +40,7 @@ pub struct HealthCheckCreate {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -48,7 +49,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -58,7 +59,8 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let health_config = create_health_check_config(options.health_check)?;
+    let health_config =
+        create_health_check_config(options.health_check, &state.config.health_check)?;
 
     let health_check = create_health_check(&state.http, &deployment_id, health_config).await?;
 
@@ -3,13 +3,18 @@ use clap::Parser;
 
 use super::utils::{delete_health_check, format_health_checks, get_all_health_checks};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete a Health Check")]
 #[group(skip)]
 pub struct Options {
-    #[clap(name = "heath-checks", help = "IDs of the Health Check")]
+    #[clap(
+        name = "heath-checks",
+        help = "IDs or `<deployment>/<index>` of the Health Checks"
+    )]
     pub health_checks: Vec<String>,
 
     #[clap(short, long, help = "Skip confirmation")]
@@ -18,7 +23,13 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let health_checks = if !options.health_checks.is_empty() {
-        options.health_checks
+        let mut ids = vec![];
+
+        for reference in &options.health_checks {
+            ids.push(state.get_health_check_by_name_or_id(reference).await?.id);
+        }
+
+        ids
     } else {
         let project_id = state.ctx.current_project_error()?.id;
 
@@ -26,7 +37,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<HEALTH_CHECKS>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -36,7 +47,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!health_checks.is_empty(), "No health checks found");
         let health_checks_fmt = format_health_checks(&health_checks, false);
 
-        let idxs = dialoguer::MultiSelect::new()
+        let idxs = prompt::MultiSelect::new("<HEALTH_CHECKS>")
             .with_prompt("Select a health check")
             .items(&health_checks_fmt)
             .interact()?;
@@ -50,7 +61,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete {} Health Checks?",
                 health_checks.len()
@@ -58,7 +69,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     let mut delete_count = 0;
@@ -1,24 +1,29 @@
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use super::utils::{format_health_checks, get_all_health_checks};
+use super::utils::{format_health_checks, get_health_checks};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "List Health Checks in a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
     #[clap(short, long, help = "Only print the IDs of the Health Checks")]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub limit: LimitOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -27,7 +32,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -37,9 +42,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let health_checks = get_all_health_checks(&state.http, &deployment_id).await?;
+    let health_checks =
+        get_health_checks(&state.http, &deployment_id, options.limit.into()).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&health_checks)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = health_checks
             .iter()
             .map(|d| d.id.as_str())
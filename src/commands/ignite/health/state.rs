@@ -4,18 +4,23 @@ use clap::Parser;
 use super::utils::{format_health_state, get_health_state};
 use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::watch::{self, WatchEvent, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Create Health Checks for a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the Deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -24,7 +29,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -34,10 +39,21 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    let health_state = get_health_state(&state.http, &deployment_id).await?;
-    let health_state_fmt = format_health_state(&health_state, true);
-
-    println!("{}", health_state_fmt.join("\n"));
-
-    Ok(())
+    let (http, output, deployment_id) = (&state.http, state.output, &deployment_id);
+
+    watch::render(
+        &state,
+        options.watch,
+        &[WatchEvent::Containers],
+        || async move {
+            let health_state = get_health_state(http, deployment_id).await?;
+
+            if let Some(output) = output.serialize(&health_state)? {
+                Ok(vec![output])
+            } else {
+                Ok(format_health_state(&health_state, true))
+            }
+        },
+    )
+    .await
 }
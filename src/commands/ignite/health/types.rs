@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+use crate::impl_paginated;
+use crate::store::config::HealthCheckConfig;
+use crate::utils::resolve::Resolvable;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
 pub struct CreateHealthCheck {
     pub initial_delay: u64,
     pub interval: u64,
@@ -28,13 +34,45 @@ impl Default for CreateHealthCheck {
     }
 }
 
-#[derive(Debug, Deserialize)]
+impl From<&HealthCheck> for CreateHealthCheck {
+    fn from(check: &HealthCheck) -> Self {
+        Self {
+            initial_delay: check.initial_delay,
+            interval: check.interval,
+            max_retries: check.max_retries,
+            path: check.path.clone(),
+            protocol: check.protocol.clone(),
+            port: u16::try_from(check.port).unwrap_or_default(),
+            timeout: check.timeout,
+            success_threshold: check.success_threshold,
+        }
+    }
+}
+
+impl CreateHealthCheck {
+    /// Built-in defaults with the configured values on top
+    pub fn with_defaults(defaults: &HealthCheckConfig) -> Self {
+        let builtin = Self::default();
+
+        Self {
+            initial_delay: defaults.initial_delay.unwrap_or(builtin.initial_delay),
+            interval: defaults.interval.unwrap_or(builtin.interval),
+            max_retries: defaults.max_retries.unwrap_or(builtin.max_retries),
+            path: defaults.path.clone().unwrap_or(builtin.path),
+            port: defaults.port.unwrap_or(builtin.port),
+            timeout: defaults.timeout.unwrap_or(builtin.timeout),
+            ..builtin
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthCheckType {
     Liveness,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct HealthCheck {
     pub id: String,
     pub deployment_id: String,
@@ -51,6 +89,14 @@ pub struct HealthCheck {
     pub type_: HealthCheckType,
 }
 
+impl Resolvable for HealthCheck {
+    const KIND: &'static str = "health check";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SingleHealthCheck {
     pub health_check: HealthCheck,
@@ -61,7 +107,9 @@ pub struct MultipleHealthChecks {
     pub health_checks: Vec<HealthCheck>,
 }
 
-#[derive(Debug, Deserialize)]
+impl_paginated!(MultipleHealthChecks => health_checks: HealthCheck);
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct HealthCheckState {
     pub state: String,
     pub container_id: String,
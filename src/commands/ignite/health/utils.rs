@@ -8,13 +8,16 @@ use super::types::{
     CreateHealthCheck, HealthCheck, HealthCheckState, MultipleHealthCheckState,
     MultipleHealthChecks, SingleHealthCheck,
 };
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
-use crate::utils::relative_time;
+use crate::store::config::HealthCheckConfig;
+use crate::utils::{prompt, relative_time};
 
 pub fn create_health_check_config(
     config: super::create::HealthCheckCreate,
+    defaults: &HealthCheckConfig,
 ) -> Result<CreateHealthCheck> {
-    let mut health_check = CreateHealthCheck::default();
+    let mut health_check = CreateHealthCheck::with_defaults(defaults);
 
     if config != Default::default() {
         update_config_from_args(&mut health_check, config)?;
@@ -57,32 +60,32 @@ pub fn update_config_from_args(
 }
 
 pub fn update_config_from_guided(config: &mut CreateHealthCheck) -> Result<()> {
-    config.port = dialoguer::Input::<u16>::new()
+    config.port = prompt::Input::<u16>::new("--port")
         .with_prompt("Port of the health check")
         .default(config.port)
         .interact()?;
 
-    config.path = dialoguer::Input::<String>::new()
+    config.path = prompt::Input::<String>::new("--path")
         .with_prompt("Path of the health check")
         .default(config.path.clone())
         .interact()?;
 
-    config.interval = dialoguer::Input::<u64>::new()
+    config.interval = prompt::Input::<u64>::new("--interval")
         .with_prompt("Interval of the health check")
         .default(config.interval)
         .interact()?;
 
-    config.timeout = dialoguer::Input::<u64>::new()
+    config.timeout = prompt::Input::<u64>::new("--timeout")
         .with_prompt("Timeout of the health check")
         .default(config.timeout)
         .interact()?;
 
-    config.max_retries = dialoguer::Input::<u64>::new()
+    config.max_retries = prompt::Input::<u64>::new("--max-retries")
         .with_prompt("Max retries of the health check")
         .default(config.max_retries)
         .interact()?;
 
-    config.initial_delay = dialoguer::Input::<u64>::new()
+    config.initial_delay = prompt::Input::<u64>::new("--initial-delay")
         .with_prompt("Initial delay of the health check")
         .default(config.initial_delay)
         .interact()?;
@@ -112,16 +115,20 @@ pub async fn get_all_health_checks(
     http: &HttpClient,
     deployment_id: &str,
 ) -> Result<Vec<HealthCheck>> {
-    let checks = http
-        .request::<MultipleHealthChecks>(
-            "GET",
-            &format!("/ignite/deployments/{deployment_id}/health-checks"),
-            None,
-        )
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Could not parse response"))?;
+    get_health_checks(http, deployment_id, PageOptions::default()).await
+}
 
-    Ok(checks.health_checks)
+pub async fn get_health_checks(
+    http: &HttpClient,
+    deployment_id: &str,
+    page: PageOptions,
+) -> Result<Vec<HealthCheck>> {
+    http.request_all::<MultipleHealthChecks>(
+        &format!("/ignite/deployments/{deployment_id}/health-checks"),
+        PageStyle::Unpaginated,
+        page,
+    )
+    .await
 }
 
 pub async fn delete_health_check(http: &HttpClient, health_check_id: &str) -> Result<()> {
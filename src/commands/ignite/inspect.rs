@@ -5,19 +5,27 @@ use clap::Parser;
 use tabwriter::TabWriter;
 
 use super::utils::get_tiers;
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_storage};
+use crate::commands::ignite::types::{Deployment, Storage, Tier};
+use crate::commands::ignite::utils::{
+    format_deployments, get_all_deployments, get_deployment, get_storage,
+};
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::watch::{self, WatchEvent, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Inspect a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "The ID or name of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
-    let mut deployment = if let Some(id_or_name) = options.deployment {
+    let deployment = if let Some(id_or_name) = options.deployment {
         state.get_deployment_by_name_or_id(&id_or_name).await?
     } else {
         let project_id = state.ctx.current_project_error()?.id;
@@ -26,7 +34,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<DEPLOYMENT>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
@@ -35,12 +43,41 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         deployments[idx].clone()
     };
 
-    let (tiers, storage) = tokio::join!(
-        get_tiers(&state.http),
-        get_storage(&state.http, &deployment.id)
-    );
-    let (tiers, storage) = (tiers?, storage?);
+    let deployment_id = deployment.id;
+    let (http, output) = (&state.http, state.output);
+    let tiers = get_tiers(http).await?;
+    let tiers = &tiers;
+
+    watch::render(
+        &state,
+        options.watch,
+        &[WatchEvent::Rollouts, WatchEvent::Containers],
+        || {
+            let deployment_id = deployment_id.clone();
+
+            async move {
+                let (deployment, storage) = tokio::join!(
+                    get_deployment(http, &deployment_id),
+                    get_storage(http, &deployment_id)
+                );
+                let deployment = deployment?;
+
+                if let Some(output) = output.serialize(&deployment)? {
+                    return Ok(vec![output]);
+                }
 
+                format_deployment(deployment, tiers, &storage?)
+            }
+        },
+    )
+    .await
+}
+
+fn format_deployment(
+    mut deployment: Deployment,
+    tiers: &[Tier],
+    storage: &Storage,
+) -> Result<Vec<String>> {
     let mut tw = TabWriter::new(vec![]);
 
     writeln!(tw, "{} ({})", deployment.name, deployment.id)?;
@@ -88,13 +125,14 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     writeln!(
         tw,
         "\tTier: {}",
-        deployment.config.resources.get_tier_name(&tiers)?
+        deployment.config.resources.get_tier_name(tiers)?
     )?;
     writeln!(
         tw,
         "\tVolume: {}",
         storage
             .volume
+            .as_ref()
             .map(|s| s.to_string())
             .unwrap_or_else(|| "None".to_string())
     )?;
@@ -103,13 +141,15 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         "\tBuild Cache: {}",
         storage
             .build_cache
+            .as_ref()
             .map(|s| s.to_string())
             .unwrap_or_else(|| "None".to_string())
     )?;
 
     tw.flush()?;
 
-    print!("{}", String::from_utf8(tw.into_inner()?)?);
-
-    Ok(())
+    Ok(String::from_utf8(tw.into_inner()?)?
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect())
 }
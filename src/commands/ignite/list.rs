@@ -1,8 +1,10 @@
 use anyhow::Result;
 use clap::Parser;
 
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
+use crate::commands::ignite::utils::{format_deployments, get_deployments};
+use crate::state::http::pagination::LimitOptions;
 use crate::state::State;
+use crate::utils::watch::{self, WatchEvent, WatchOptions};
 
 #[derive(Debug, Parser)]
 #[clap(about = "List all deployments")]
@@ -10,26 +12,40 @@ use crate::state::State;
 pub struct Options {
     #[clap(short, long, help = "Only print the IDs of the deployments")]
     pub quiet: bool,
+
+    #[clap(flatten)]
+    pub limit: LimitOptions,
+
+    #[clap(flatten)]
+    pub watch: WatchOptions,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
-    let deployments = get_all_deployments(&state.http, &project_id).await?;
-
-    if options.quiet {
-        let ids = deployments
-            .iter()
-            .map(|d| d.id.as_str())
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        println!("{ids}");
-    } else {
-        let deployments_fmt = format_deployments(&deployments, true);
-
-        println!("{}", deployments_fmt.join("\n"));
-    }
-
-    Ok(())
+    let (http, output, project_id) = (&state.http, state.output, &project_id);
+
+    watch::render(
+        &state,
+        options.watch,
+        &[WatchEvent::Rollouts, WatchEvent::Containers],
+        || async move {
+            let deployments = get_deployments(http, project_id, options.limit.into()).await?;
+
+            if let Some(output) = output.serialize(&deployments)? {
+                Ok(vec![output])
+            } else if options.quiet {
+                let ids = deployments
+                    .iter()
+                    .map(|d| d.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                Ok(vec![ids])
+            } else {
+                Ok(format_deployments(&deployments, true))
+            }
+        },
+    )
+    .await
 }
@@ -1,9 +1,10 @@
 pub mod builds;
 pub mod create;
 mod delete;
+mod export;
 pub mod from_compose;
 mod get_env;
-mod health;
+pub mod health;
 mod inspect;
 mod list;
 mod promote;
@@ -30,6 +31,7 @@ pub enum Commands {
     Update(update::Options),
     #[clap(alias = "info")]
     Inspect(inspect::Options),
+    Export(export::Options),
     #[clap(alias = "rollouts")]
     Rollout(rollout::Options),
     Scale(scale::Options),
@@ -68,6 +70,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         Commands::Delete(options) => delete::handle(options, state).await,
         Commands::Update(options) => update::handle(options, state).await,
         Commands::Inspect(options) => inspect::handle(options, state).await,
+        Commands::Export(options) => export::handle(options, state).await,
         Commands::Rollout(options) => rollout::handle(options, state).await,
         Commands::Scale(options) => scale::handle(options, state).await,
         Commands::GetEnv(options) => get_env::handle(options, state).await,
@@ -5,21 +5,22 @@ use super::utils::{format_deployments, get_all_deployments, promote};
 use crate::commands::ignite::builds::types::BuildState;
 use crate::commands::ignite::builds::utils::get_all_builds;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Rollback containers in a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 
-    #[clap(help = "ID of the build to rollback to")]
+    #[clap(help = "ID or index of the build to rollback to, `0` is the latest build")]
     pub build: Option<String>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment_id = match options.deployment {
-        Some(id) => id,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?.id,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -28,7 +29,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -39,7 +40,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     let build_id = match options.build {
-        Some(id) => id,
+        Some(reference) => {
+            state
+                .get_deployment_build_by_name_or_id(&deployment_id, &reference)
+                .await?
+                .id
+        }
 
         None => {
             let builds = get_all_builds(&state.http, &deployment_id)
@@ -49,7 +55,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 .collect::<Vec<_>>();
             ensure!(!builds.is_empty(), "No successful builds found");
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<BUILD>")
                 .with_prompt("Select a build")
                 .items(&builds.iter().map(|b| &b.id).collect::<Vec<_>>())
                 .default(0)
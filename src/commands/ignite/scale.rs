@@ -2,14 +2,14 @@ use anyhow::{ensure, Result};
 use clap::Parser;
 
 use super::utils::{format_deployments, get_all_deployments, scale};
-use crate::commands::ignite::utils::get_deployment;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Scale a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment to scale")]
+    #[clap(help = "Name or ID of the deployment to scale")]
     pub deployment: Option<String>,
 
     #[clap(help = "Number of replicas to scale to")]
@@ -18,7 +18,7 @@ pub struct Options {
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let deployment = match options.deployment {
-        Some(id) => get_deployment(&state.http, &id).await?,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?,
 
         None => {
             let project_id = state.ctx.current_project_error()?.id;
@@ -27,7 +27,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -39,7 +39,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     let scale_count = match options.scale {
         Some(scale) => scale,
-        None => dialoguer::Input::<u64>::new()
+        None => prompt::Input::<u64>::new("<SCALE>")
             .with_prompt("Enter the number of containers to scale to")
             .default(deployment.container_count)
             .interact()?,
@@ -14,7 +14,7 @@ use crate::commands::ignite::utils::{
 };
 use crate::commands::projects::utils::format_project;
 use crate::state::State;
-use crate::utils::urlify;
+use crate::utils::{prompt, urlify};
 
 #[derive(Debug, Parser, Default, PartialEq, Clone)]
 #[clap(about = "Create a new deployment")]
@@ -42,7 +42,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     } else {
         let premade_fmt = format_premade(&premades, false)?;
 
-        let selection = dialoguer::Select::new()
+        let selection = prompt::Select::new("<TEMPLATE>")
             .with_prompt("Select a template")
             .items(&premade_fmt)
             .default(0)
@@ -78,7 +78,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                     validator,
                     required,
                 } => {
-                    let mut input = dialoguer::Input::<String>::new();
+                    let mut input = prompt::Input::<String>::new(&field.title);
 
                     if let Some(default) = default {
                         input.default(default.clone());
@@ -157,7 +157,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                         .take(((max - min) / increment) as usize)
                         .collect::<Vec<_>>();
 
-                    let mut input = dialoguer::Select::new();
+                    let mut input = prompt::Select::new(&field.title);
 
                     input.default(
                         items
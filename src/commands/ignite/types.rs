@@ -4,10 +4,13 @@ use std::str::FromStr;
 use std::vec;
 
 use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::containers::types::ContainerType;
+use crate::impl_paginated;
 use crate::utils::parse_key_val;
+use crate::utils::resolve::Resolvable;
 use crate::utils::size::{parse_size, unit_multiplier, user_friendly_size};
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -75,7 +78,7 @@ impl RamSizes {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
 pub struct Resources {
     pub vcpu: f64,
     pub ram: String,
@@ -120,7 +123,7 @@ impl From<TierResources> for Resources {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, JsonSchema)]
 pub enum ScalingStrategy {
     #[default]
     #[serde(rename = "manual")]
@@ -153,7 +156,7 @@ impl ScalingStrategy {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, JsonSchema)]
 pub struct Image {
     pub name: String,
 }
@@ -187,6 +190,18 @@ pub struct Deployment {
     pub metadata: Option<Metadata>,
 }
 
+impl Resolvable for Deployment {
+    const KIND: &'static str = "deployment";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
 pub struct Metadata {
     pub container_port_mappings: Option<HashMap<String, Vec<String>>>,
@@ -221,7 +236,10 @@ pub struct MultipleDeployments {
     pub deployments: Vec<Deployment>,
 }
 
-#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+impl_paginated!(MultipleDeployments => deployments: Deployment);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, JsonSchema)]
+#[serde(default)]
 pub struct CreateDeployment {
     pub restart_policy: Option<RestartPolicy>,
     pub container_strategy: ScalingStrategy,
@@ -277,7 +295,7 @@ pub struct ScaleRequest {
     pub scale: u64,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, JsonSchema)]
 pub enum RestartPolicy {
     #[serde(rename = "never")]
     Never,
@@ -312,7 +330,7 @@ impl RestartPolicy {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema)]
 pub struct Volume {
     pub fs: VolumeFs,
     #[serde(rename = "mountpath")]
@@ -330,7 +348,7 @@ impl Default for Volume {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum VolumeFs {
     #[default]
@@ -1,18 +1,21 @@
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 
 use super::create::Options as CreateOptions;
+use crate::commands::apply::utils::plan_deployment_update;
 use crate::commands::ignite::utils::{
     format_deployments, get_all_deployments, rollout, scale, update_deployment,
     update_deployment_config,
 };
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Update a deployment")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment to update")]
+    #[clap(help = "Name or ID of the deployment to update")]
     deployment: Option<String>,
 
     #[clap(flatten)]
@@ -20,6 +23,9 @@ pub struct Options {
 
     #[clap(long, help = "Do not roll out the changes, only build")]
     no_rollout: bool,
+
+    #[clap(short, long, help = "Skip confirmation of the changes")]
+    yes: bool,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
@@ -33,7 +39,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             ensure!(!deployments.is_empty(), "No deployments found");
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -56,6 +62,34 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     )
     .await?;
 
+    let plan = plan_deployment_update(
+        &state,
+        &project,
+        &old_deployment,
+        deployment_config.clone(),
+        container_options.containers,
+    )
+    .await?;
+
+    if plan.is_empty() {
+        log::info!("Nothing to update");
+
+        return Ok(());
+    }
+
+    println!("{}", plan.format()?.join("\n"));
+
+    // scripts that update without input keep working as before the plan was shown
+    if !options.yes
+        && prompt::is_interactive()
+        && !prompt::Confirm::new("--yes")
+            .with_prompt("Apply these changes?")
+            .interact_opt()?
+            .unwrap_or(false)
+    {
+        bail!(HopError::cancelled());
+    }
+
     let mut deployment = update_deployment(&state.http, &old_deployment.id, &deployment_config)
         .await
         .map_err(|e| anyhow!("Failed to update deployment: {}", e))?;
@@ -20,23 +20,29 @@ use crate::commands::ignite::types::{
 };
 use crate::commands::projects::types::{Project, Sku};
 use crate::commands::projects::utils::{get_quotas, get_skus};
+use crate::errors::HopError;
+use crate::state::http::pagination::{PageOptions, PageStyle};
 use crate::state::http::HttpClient;
 use crate::utils::size::{parse_size, unit_multiplier};
-use crate::utils::{ask_question_iter, parse_key_val};
+use crate::utils::{ask_question_iter, parse_key_val, prompt};
 
 pub const WEB_IGNITE_URL: &str = "https://console.hop.io/ignite";
 
 pub async fn get_all_deployments(http: &HttpClient, project_id: &str) -> Result<Vec<Deployment>> {
-    let response = http
-        .request::<MultipleDeployments>(
-            "GET",
-            &format!("/ignite/deployments?project={project_id}"),
-            None,
-        )
-        .await?
-        .ok_or_else(|| anyhow!("Failed to parse response"))?;
+    get_deployments(http, project_id, PageOptions::default()).await
+}
 
-    Ok(response.deployments)
+pub async fn get_deployments(
+    http: &HttpClient,
+    project_id: &str,
+    page: PageOptions,
+) -> Result<Vec<Deployment>> {
+    http.request_all::<MultipleDeployments>(
+        &format!("/ignite/deployments?project={project_id}"),
+        PageStyle::Unpaginated,
+        page,
+    )
+    .await
 }
 
 pub async fn get_deployment(http: &HttpClient, deployment_id: &str) -> Result<Deployment> {
@@ -263,12 +269,12 @@ pub async fn update_deployment_config(
     );
 
     if is_visual
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--yes")
             .with_prompt("Do you want to continue?")
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("User aborted");
+        bail!(HopError::cancelled());
     }
 
     Ok(configs)
@@ -453,7 +459,7 @@ async fn update_config_visual(
             .or_else(|| deployment_config.name.clone())
             .unwrap_or_default();
 
-        dialoguer::Input::<String>::new()
+        prompt::Input::<String>::new("--name")
             .with_prompt("Deployment name")
             .default(back_name.clone())
             .show_default(!back_name.is_empty())
@@ -478,7 +484,7 @@ async fn update_config_visual(
         } else {
             let old_name = deployment_config.image.clone().unwrap_or_default().name;
 
-            let new_name = dialoguer::Input::<String>::new()
+            let new_name = prompt::Input::<String>::new("--image")
             .with_prompt("Image name")
             .default(old_name.clone())
             .show_default(!old_name.is_empty())
@@ -520,7 +526,7 @@ async fn update_config_visual(
     }
 
     deployment_config.resources = {
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--tier")
             .with_prompt("Select a tier that will suit you well")
             .default(0)
             .items(&tiers.iter().map(|t| t.to_string()).collect::<Vec<String>>())
@@ -535,7 +541,7 @@ async fn update_config_visual(
 
             let mut resources = Resources::default();
 
-            resources.vcpu = dialoguer::Input::<f64>::new()
+            resources.vcpu = prompt::Input::<f64>::new("--cpu")
                 .with_prompt("CPUs")
                 .default(deployment_config.resources.vcpu)
                 .show_default(is_update)
@@ -543,6 +549,7 @@ async fn update_config_visual(
                 .interact_text()?;
 
             resources.ram = ask_question_iter(
+                "--ram",
                 "Memory",
                 &RamSizes::values(),
                 Some(deployment_config.resources.ram.parse().unwrap_or_default()),
@@ -563,7 +570,7 @@ async fn update_config_visual(
     // volume only will be some and is_update to false in the `from-compose` command
     if (!is_update && deployment_config.volume.is_some())
         || (!(is_update && deployment_config.image.is_some())
-            && dialoguer::Confirm::new()
+            && prompt::Confirm::new("--volume-mount")
                 .with_prompt("Would you like to attach a volume?")
                 .default(false)
                 .interact()?)
@@ -575,15 +582,20 @@ async fn update_config_visual(
 
         let mut volume = deployment_config.volume.clone().unwrap_or_default();
 
-        volume.size = dialoguer::Input::<String>::new()
+        volume.size = prompt::Input::<String>::new("--volume-size")
             .with_prompt("Volume size")
             .default(volume.size)
             .validate_with(|size: &String| -> Result<()> { parse_size(size).map(|_| ()) })
             .interact_text()?;
 
-        volume.fs = ask_question_iter("Filesystem", &VolumeFs::values(), Some(volume.fs.clone()))?;
+        volume.fs = ask_question_iter(
+            "--volume-fs",
+            "Filesystem",
+            &VolumeFs::values(),
+            Some(volume.fs.clone()),
+        )?;
 
-        volume.mount_path = dialoguer::Input::<String>::new()
+        volume.mount_path = prompt::Input::<String>::new("--volume-mount")
             .with_prompt("Mount path")
             .default(volume.mount_path)
             .interact_text()?;
@@ -595,7 +607,7 @@ async fn update_config_visual(
 
     if deployment_config.type_ != Some(ContainerType::Stateful) {
         container_options.containers = Some(
-            dialoguer::Input::<u64>::new()
+            prompt::Input::<u64>::new("--containers")
                 .with_prompt("Container amount to start")
                 .default(container_options.containers.unwrap_or(1))
                 .validate_with(|containers: &u64| -> Result<(), &str> {
@@ -613,15 +625,17 @@ async fn update_config_visual(
 
     deployment_config.env.extend(get_multiple_envs()?);
 
-    if dialoguer::Confirm::new()
-        .with_prompt("Do you want to change advanced settings?")
-        .default(false)
-        .interact_opt()?
-        .unwrap_or(false)
+    // every advanced setting has its own flag, without input they keep their defaults
+    if prompt::is_interactive()
+        && prompt::Confirm::new("--entrypoint")
+            .with_prompt("Do you want to change advanced settings?")
+            .default(false)
+            .interact_opt()?
+            .unwrap_or(false)
     {
         match deployment_config.type_ {
             Some(ContainerType::Persistent) => {
-                if dialoguer::Confirm::new()
+                if prompt::Confirm::new("--rm")
                     .with_prompt("Would you like your containers to be deleted when they exit?")
                     .default(false)
                     .interact()?
@@ -631,7 +645,7 @@ async fn update_config_visual(
             }
 
             Some(ContainerType::Ephemeral) => {
-                if dialoguer::Confirm::new()
+                if prompt::Confirm::new("--rm")
                     .with_prompt("Would you like your containers to be persisted when they exit?")
                     .default(false)
                     .interact()?
@@ -643,7 +657,7 @@ async fn update_config_visual(
             _ => {}
         }
 
-        if dialoguer::Confirm::new()
+        if prompt::Confirm::new("--entrypoint")
             .with_prompt("Do you want to specify a custom entrypoint?")
             .default(false)
             .interact()?
@@ -655,7 +669,7 @@ async fn update_config_visual(
                 .join(" ");
 
             deployment_config.entrypoint = Some(
-                dialoguer::Input::<String>::new()
+                prompt::Input::<String>::new("--entrypoint")
                     .with_prompt("Entrypoint")
                     .show_default(is_update && !ep.is_empty())
                     .default(ep)
@@ -664,7 +678,7 @@ async fn update_config_visual(
             );
         }
 
-        if dialoguer::Confirm::new()
+        if prompt::Confirm::new("--command")
             .with_prompt("Do you want to specify a custom command?")
             .default(false)
             .interact()?
@@ -676,7 +690,7 @@ async fn update_config_visual(
                 .join(" ");
 
             deployment_config.command = Some(
-                dialoguer::Input::<String>::new()
+                prompt::Input::<String>::new("--command")
                     .with_prompt("Command")
                     .show_default(is_update && !cmd.is_empty())
                     .default(cmd)
@@ -686,12 +700,13 @@ async fn update_config_visual(
         }
 
         if deployment_config.type_ != Some(ContainerType::Ephemeral)
-            && dialoguer::Confirm::new()
+            && prompt::Confirm::new("--restart-policy")
                 .with_prompt("Do you want to specify a restart policy for your containers?")
                 .default(false)
                 .interact()?
         {
             deployment_config.restart_policy = Some(ask_question_iter(
+                "--restart-policy",
                 "Select a restart policy that will be used for your containers",
                 &RestartPolicy::values(),
                 deployment_config.restart_policy.clone(),
@@ -715,7 +730,7 @@ async fn update_config_visual(
 fn get_multiple_envs() -> Result<HashMap<String, String>> {
     let mut env = HashMap::new();
 
-    let confirm_ = dialoguer::Confirm::new()
+    let confirm_ = prompt::Confirm::new("--env")
         .with_prompt("Add environment variables?")
         .default(false)
         .interact_opt()?
@@ -734,7 +749,7 @@ fn get_multiple_envs() -> Result<HashMap<String, String>> {
             break;
         }
 
-        if !dialoguer::Confirm::new()
+        if !prompt::Confirm::new("--env")
             .with_prompt("Add another environment variable?")
             .default(false)
             .interact_opt()?
@@ -748,7 +763,7 @@ fn get_multiple_envs() -> Result<HashMap<String, String>> {
 }
 
 fn get_env_from_input() -> Option<(String, String)> {
-    let key = dialoguer::Input::<String>::new()
+    let key = prompt::Input::<String>::new("--env")
         .with_prompt("Key")
         .interact_text();
 
@@ -757,7 +772,7 @@ fn get_env_from_input() -> Option<(String, String)> {
         Err(_) => return None,
     };
 
-    let value = dialoguer::Input::<String>::new()
+    let value = prompt::Input::<String>::new("--env")
         .with_prompt("Value")
         .interact_text();
 
@@ -4,11 +4,12 @@ use std::path::PathBuf;
 use anyhow::{ensure, Result};
 use clap::Parser;
 
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
+use crate::commands::ignite::utils::{format_deployments, get_all_deployments};
 use crate::commands::projects::utils::format_project;
 use crate::config::EXEC_NAME;
 use crate::state::State;
 use crate::store::hopfile::HopFile;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Link an existing deployment to a hopfile")]
@@ -20,7 +21,7 @@ pub struct Options {
     )]
     path: Option<PathBuf>,
 
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     deployment: Option<String>,
 }
 
@@ -33,7 +34,8 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
 
     ensure!(dir.is_dir(), "{dir:?} is not a directory");
 
-    if HopFile::find(dir.clone()).await.is_some() {
+    // an environment that is not in the hopfile yet is added to it below
+    if let Ok(Some(_)) = HopFile::find(dir.clone()).await {
         log::warn!("A hopfile was found {dir:?}, did you mean to `{EXEC_NAME} deploy`?");
     }
 
@@ -42,14 +44,14 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     log::info!("Project: {}", format_project(&project));
 
     let deployment = match options.deployment {
-        Some(id) => get_deployment(&state.http, &id).await?,
+        Some(name_or_id) => state.get_deployment_by_name_or_id(&name_or_id).await?,
 
         None => {
             let deployments = get_all_deployments(&state.http, &project.id).await?;
 
             let deployments_fmt = format_deployments(&deployments, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<DEPLOYMENT>")
                 .with_prompt("Select a deployment")
                 .items(&deployments_fmt)
                 .default(0)
@@ -64,9 +66,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         }
     };
 
-    HopFile::new(dir.join("hop.yml"), &project.id, &deployment.id)
-        .save()
-        .await?;
+    HopFile::link(&dir, &project.id, &deployment.id).await?;
 
     log::info!(
         "Deployment `{}` ({}) linked",
@@ -1,20 +1,27 @@
+mod api;
+pub mod apply;
 pub mod auth;
-mod channels;
+pub mod channels;
 mod completions;
+mod config;
 pub mod containers;
+mod context;
 pub mod deploy;
-mod domains;
-mod gateways;
+mod diff;
+pub mod domains;
+pub mod gateways;
 pub mod ignite;
 mod link;
 mod oops;
 mod payment;
+mod plugins;
 pub mod projects;
-mod secrets;
+pub mod secrets;
 mod tunnel;
 pub mod update;
+mod validate;
 mod volumes;
-mod webhooks;
+pub mod webhooks;
 mod whoami;
 
 use anyhow::Result;
@@ -32,6 +39,9 @@ pub enum Commands {
     #[clap(alias = "secret")]
     Secrets(secrets::Options),
     Deploy(deploy::Options),
+    Apply(apply::Options),
+    Diff(diff::Options),
+    Validate(validate::Options),
     #[clap(alias = "info", alias = "ctx")]
     Whoami(whoami::Options),
     Ignite(ignite::Options),
@@ -46,6 +56,8 @@ pub enum Commands {
     Domains(domains::Options),
     #[clap(alias = "complete", hide = cfg!(not(feature = "update")))]
     Completions(completions::Options),
+    #[clap(name = "complete-resources", hide = true)]
+    DynamicCompletions(completions::dynamic::Options),
     #[clap(alias = "channel", alias = "ch")]
     Channels(channels::Options),
     Oops(oops::Options),
@@ -65,24 +77,45 @@ pub enum Commands {
     #[clap(alias = "compose")]
     FromCompose(from_compose::Options),
     Backup(backup::Options),
+    #[clap(alias = "contexts")]
+    Context(context::Options),
+    Api(api::Options),
+    Config(config::Options),
+    #[clap(alias = "plugin")]
+    Plugins(plugins::Options),
+    #[clap(external_subcommand)]
+    External(Vec<String>),
 }
 
 pub async fn handle_command(command: Commands, mut state: State) -> Result<()> {
     match command {
         Commands::Auth(options) => auth::handle(options, state).await,
+        Commands::Context(options) => context::handle(options, state).await,
+        Commands::Config(options) => config::handle(options, state).await,
+        Commands::Plugins(options) => plugins::handle(&options, &state),
+        Commands::Validate(options) => validate::handle(options).await,
+        Commands::External(args) => plugins::run_plugin(args, state).await,
         #[cfg(feature = "update")]
         Commands::Update(options) => update::handle(options, state).await,
         Commands::Completions(options) => {
             completions::handle(options, state);
             Ok(())
         }
+        Commands::DynamicCompletions(options) => completions::dynamic::handle(options, state).await,
 
         authorized_command => {
             // login so these commands can run
             state.login(None).await?;
 
             match authorized_command {
-                Commands::Auth(_) | Commands::Completions(_) => {
+                Commands::Auth(_)
+                | Commands::Context(_)
+                | Commands::Config(_)
+                | Commands::Completions(_)
+                | Commands::DynamicCompletions(_)
+                | Commands::Plugins(_)
+                | Commands::Validate(_)
+                | Commands::External(_) => {
                     unreachable!()
                 }
 
@@ -93,6 +126,8 @@ pub async fn handle_command(command: Commands, mut state: State) -> Result<()> {
                 Commands::Projects(options) => projects::handle(options, state).await,
                 Commands::Secrets(options) => secrets::handle(options, state).await,
                 Commands::Deploy(options) => deploy::handle(options, state).await,
+                Commands::Apply(options) => apply::handle(options, state).await,
+                Commands::Diff(options) => diff::handle(options, state).await,
                 Commands::Whoami(options) => whoami::handle(&options, state),
                 Commands::Ignite(options) => ignite::handle(options, state).await,
                 Commands::Link(options) => link::handle(options, state).await,
@@ -106,6 +141,7 @@ pub async fn handle_command(command: Commands, mut state: State) -> Result<()> {
                 Commands::Volumes(options) => volumes::handle(options, state).await,
                 Commands::Backup(options) => backup::handle(options, state).await,
                 Commands::Webhooks(options) => webhooks::handle(options, state).await,
+                Commands::Api(options) => api::handle(options, state).await,
             }
         }
     }
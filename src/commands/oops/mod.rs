@@ -7,20 +7,21 @@ use super::ignite::utils::{format_deployments, get_all_deployments, promote};
 use crate::commands::projects::utils::format_project;
 use crate::state::State;
 use crate::store::hopfile::HopFile;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Instantly roll back your deployment to a previous build")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "ID of the deployment")]
+    #[clap(help = "Name or ID of the deployment")]
     pub deployment: Option<String>,
 }
 
 pub async fn handle(options: &Options, state: State) -> Result<()> {
-    let deployment_id = if let Some(ref id) = options.deployment {
-        id.clone()
-    } else if let Some(hopfile) = HopFile::find_current().await {
-        hopfile.config.deployment_id
+    let deployment_id = if let Some(ref name_or_id) = options.deployment {
+        state.get_deployment_by_name_or_id(name_or_id).await?.id
+    } else if let Some(hopfile) = HopFile::find_current().await? {
+        state.get_hopfile_deployment(&hopfile).await?.id
     } else {
         let project = state.ctx.current_project_error()?;
 
@@ -30,7 +31,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
         ensure!(!deployments.is_empty(), "No deployments found.");
         let deployments_fmt = format_deployments(&deployments, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("<DEPLOYMENT>")
             .with_prompt("Select a deployment")
             .items(&deployments_fmt)
             .default(0)
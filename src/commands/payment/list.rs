@@ -15,7 +15,9 @@ pub struct Options {
 pub async fn handle(options: &Options, state: &State) -> Result<()> {
     let payment_methods = get_all_payment_methods(&state.http).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&payment_methods)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = payment_methods
             .iter()
             .map(|d| d.id.as_str())
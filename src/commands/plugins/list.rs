@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::Parser;
+
+use super::utils::{discover_plugins, format_plugins, plugin_dirs};
+use crate::state::State;
+
+#[derive(Debug, Parser)]
+#[clap(about = "List all plugins found in `~/.hop/plugins` and `PATH`")]
+#[group(skip)]
+pub struct Options {
+    #[clap(short, long, help = "Only print the names of the plugins")]
+    pub quiet: bool,
+}
+
+pub fn handle(options: &Options, state: &State) -> Result<()> {
+    let plugins = discover_plugins(&plugin_dirs());
+
+    if let Some(output) = state
+        .output
+        .serialize(&plugins.values().collect::<Vec<_>>())?
+    {
+        println!("{output}");
+    } else if options.quiet {
+        let names = plugins
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        println!("{names}");
+    } else if plugins.is_empty() {
+        log::info!("No plugins found, plugins are executables named `hop-<name>`");
+    } else {
+        let plugins_fmt = format_plugins(&plugins, true);
+
+        println!("{}", plugins_fmt.join("\n"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+mod list;
+pub mod utils;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use tokio::process::Command;
+
+use self::utils::{find_plugin, plugin_dirs};
+use crate::config::EXEC_NAME;
+use crate::errors::{ErrorKind, HopError};
+use crate::state::State;
+use crate::store::hopfile::{self, HopFile};
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    #[clap(name = "ls", alias = "list")]
+    List(list::Options),
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Manage plugins, executables named `hop-<name>` that run as `hop <name>`")]
+#[group(skip)]
+pub struct Options {
+    #[clap(subcommand)]
+    pub commands: Commands,
+}
+
+pub fn handle(options: &Options, state: &State) -> Result<()> {
+    match &options.commands {
+        Commands::List(options) => list::handle(options, state),
+    }
+}
+
+/// Runs the plugin for an unknown subcommand, the current
+/// token, project, API URL and Hopfile are passed as environment variables
+pub async fn run_plugin(args: Vec<String>, mut state: State) -> Result<()> {
+    let (name, args) = args.split_first().context("Missing plugin name")?;
+
+    let Some(plugin) = find_plugin(&plugin_dirs(), name) else {
+        bail!(HopError::new(
+            ErrorKind::Usage,
+            format!(
+                "Unknown command `{name}`, no plugin named `{}{name}` was found. Run `{EXEC_NAME} --help` to see all commands",
+                utils::PLUGIN_PREFIX
+            )
+        ));
+    };
+
+    log::debug!("Running plugin `{name}` from {}", plugin.path.display());
+
+    let mut command = Command::new(&plugin.path);
+
+    command
+        .args(args)
+        .env("HOP_API_URL", &state.http.base_url)
+        .env("HOP_CLI_VERSION", crate::config::VERSION);
+
+    // plugins can be used without an account, the token is only validated when there is one
+    if let Err(error) = state.unlock_auth().await {
+        log::debug!("Running the plugin without a token: {error}");
+    }
+
+    if state.token().is_some() {
+        state.login(None).await?;
+    }
+
+    if let Some(token) = state.token() {
+        command.env("HOP_TOKEN", token);
+    }
+
+    if let Some(project) = state.ctx.current_project() {
+        command.env("HOP_PROJECT_ID", project.id);
+    }
+
+    // the hopfile is only context for the plugin, a broken one should not stop it
+    match HopFile::find_current().await {
+        Ok(Some(hopfile)) => {
+            command.env("HOP_HOPFILE", hopfile.path);
+        }
+
+        Ok(None) => {}
+
+        Err(error) => log::debug!("Running the plugin without a hopfile: {error}"),
+    }
+
+    if let Some(environment) = hopfile::environment() {
+        command.env("HOP_ENV", environment);
+    }
+
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("Failed to run plugin `{}`", plugin.path.display()))?;
+
+    if !status.success() {
+        // forward the exit code so plugins behave like any other command
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tabwriter::TabWriter;
+
+use crate::store::utils::home_path;
+
+/// Prefix of plugin executables, `hop foo` runs `hop-foo`
+pub const PLUGIN_PREFIX: &str = "hop-";
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Directories searched for plugins, `~/.hop/plugins` takes precedence over `PATH`
+pub fn plugin_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+
+    if let Ok(path) = home_path(".hop/plugins") {
+        dirs.push(path);
+    }
+
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+
+    dirs
+}
+
+/// All plugins by name, the first match wins when a name is found in multiple directories
+pub fn discover_plugins(dirs: &[PathBuf]) -> BTreeMap<String, Plugin> {
+    let mut plugins = BTreeMap::new();
+
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            let Some(name) = plugin_name(&path) else {
+                continue;
+            };
+
+            if !is_executable(&path) {
+                continue;
+            }
+
+            plugins.entry(name.clone()).or_insert(Plugin { name, path });
+        }
+    }
+
+    plugins
+}
+
+pub fn find_plugin(dirs: &[PathBuf], name: &str) -> Option<Plugin> {
+    discover_plugins(dirs).remove(name)
+}
+
+fn plugin_name(path: &Path) -> Option<String> {
+    let name = if cfg!(windows) {
+        path.file_stem()?
+    } else {
+        path.file_name()?
+    };
+
+    name.to_str()?
+        .strip_prefix(PLUGIN_PREFIX)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "exe" | "bat" | "cmd"))
+}
+
+pub fn format_plugins(plugins: &BTreeMap<String, Plugin>, title: bool) -> Vec<String> {
+    let mut tw = TabWriter::new(vec![]);
+
+    if title {
+        writeln!(&mut tw, "NAME\tPATH").unwrap();
+    }
+
+    for plugin in plugins.values() {
+        writeln!(&mut tw, "{}\t{}", plugin.name, plugin.path.display()).unwrap();
+    }
+
+    String::from_utf8(tw.into_inner().unwrap())
+        .unwrap()
+        .lines()
+        .map(std::string::ToString::to_string)
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::*;
+
+    fn create(dir: &Path, name: &str, mode: u32) {
+        let path = dir.join(name);
+
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn test_discover_plugins() {
+        let root = std::env::temp_dir().join(format!("hop-plugins-{}", std::process::id()));
+        let (first, second) = (root.join("first"), root.join("second"));
+
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+
+        create(&first, "hop-foo", 0o755);
+        create(&first, "hop-noexec", 0o644);
+        create(&first, "other", 0o755);
+        create(&second, "hop-foo", 0o755);
+        create(&second, "hop-bar", 0o755);
+
+        let plugins = discover_plugins(&[first.clone(), second]);
+
+        assert_eq!(plugins.keys().collect::<Vec<_>>(), vec!["bar", "foo"]);
+        assert_eq!(plugins["foo"].path, first.join("hop-foo"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -7,6 +7,7 @@ use crate::commands::projects::create::utils::get_payment_method_from_user;
 use crate::commands::projects::utils::{create_project, format_project, validate_namespace};
 use crate::state::State;
 use crate::store::Store;
+use crate::utils::prompt;
 
 // TODO: replace when ../new path is implemented
 const WEB_PAYMENTS_URL: &str = "https://console.hop.io/settings/cards";
@@ -21,13 +22,15 @@ pub struct Options {
     name: Option<String>,
     #[clap(short, long, help = "Set as default project")]
     default: bool,
+    #[clap(long = "payment-method", help = "ID of the payment method to use")]
+    payment_method: Option<String>,
 }
 
 pub async fn handle(options: Options, mut state: State) -> Result<()> {
     let namespace = if let Some(namespace) = options.namespace {
         namespace
     } else {
-        dialoguer::Input::new()
+        prompt::Input::new("<NAMESPACE>")
             .with_prompt("Namespace of the project")
             .validate_with(|input: &String| -> Result<()> { validate_namespace(input) })
             .interact_text()?
@@ -36,7 +39,7 @@ pub async fn handle(options: Options, mut state: State) -> Result<()> {
     let name = if let Some(name) = options.name {
         name
     } else {
-        dialoguer::Input::new()
+        prompt::Input::new("<NAME>")
             .with_prompt("Name of the project")
             .validate_with(|input: &String| -> Result<()> {
                 if input.len() > 32 {
@@ -48,7 +51,10 @@ pub async fn handle(options: Options, mut state: State) -> Result<()> {
             .interact_text()?
     };
 
-    let payment_method_id = get_payment_method_from_user(&state.http).await?;
+    let payment_method_id = match options.payment_method {
+        Some(payment_method) => payment_method,
+        None => get_payment_method_from_user(&state.http).await?,
+    };
 
     let project = create_project(&state.http, &name, &namespace, &payment_method_id).await?;
 
@@ -9,7 +9,7 @@ use super::WEB_PAYMENTS_URL;
 use crate::commands::payment::utils::{format_payment_methods, get_all_payment_methods};
 use crate::state::http::HttpClient;
 use crate::utils::browser::listen_for_callback;
-use crate::utils::urlify;
+use crate::utils::{prompt, urlify};
 
 pub async fn get_payment_method_from_user(http: &HttpClient) -> Result<String> {
     loop {
@@ -17,7 +17,7 @@ pub async fn get_payment_method_from_user(http: &HttpClient) -> Result<String> {
         let mut payment_methods_fmt = format_payment_methods(&payment_methods, false)?;
         payment_methods_fmt.push("New payment method".to_string());
 
-        let payment_method_idx = dialoguer::Select::new()
+        let payment_method_idx = prompt::Select::new("--payment-method")
             .with_prompt("Select a payment method")
             .items(&payment_methods_fmt)
             .default(0)
@@ -4,8 +4,10 @@ use serde_json::Value;
 
 use super::utils::format_projects;
 use crate::commands::projects::utils::format_project;
+use crate::errors::{ErrorKind, HopError};
 use crate::state::State;
 use crate::store::Store;
+use crate::utils::prompt;
 
 static CONFIRM_DELETE_PROJECT_MESSAGE: &str = "I am sure I want to delete the project named ";
 
@@ -31,7 +33,7 @@ pub async fn handle(options: Options, mut state: State) -> Result<()> {
         None => {
             let projects_fmt = format_projects(&projects, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<PROJECT>")
                 .with_prompt("Select a project")
                 .items(&projects_fmt)
                 .default(if let Some(current) = state.ctx.current_project() {
@@ -54,15 +56,17 @@ pub async fn handle(options: Options, mut state: State) -> Result<()> {
             CONFIRM_DELETE_PROJECT_MESSAGE, project.name
         );
 
-        let output = dialoguer::Input::<String>::new()
+        let output = prompt::Input::<String>::new("--force")
             .with_prompt("Message")
             .interact_text()
             .context("Failed to confirm deletion")?;
 
         ensure!(
             output == CONFIRM_DELETE_PROJECT_MESSAGE.to_string() + &project.name,
-            "Aborted deletion of `{}`",
-            project.name
+            HopError::new(
+                ErrorKind::Cancelled,
+                format!("Aborted deletion of `{}`", project.name)
+            )
         );
     }
 
@@ -12,6 +12,12 @@ pub struct Options {}
 pub fn handle(_options: &Options, state: State) -> Result<()> {
     let project = state.ctx.current_project_error()?;
 
+    if let Some(output) = state.output.serialize(&project)? {
+        println!("{output}");
+
+        return Ok(());
+    }
+
     log::info!("Project: {}", format_project(&project));
 
     Ok(())
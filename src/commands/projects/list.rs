@@ -15,7 +15,9 @@ pub struct Options {
 pub fn handle(options: Options, state: State) -> Result<()> {
     let projects = state.ctx.current.context("You are not logged in")?.projects;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&projects)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = projects
             .iter()
             .map(|d| d.id.as_str())
@@ -4,6 +4,7 @@ use clap::Parser;
 use crate::commands::projects::utils::{format_project, format_projects};
 use crate::state::State;
 use crate::store::Store;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Switch to a different project")]
@@ -24,7 +25,7 @@ pub async fn handle(options: Options, mut state: State) -> Result<()> {
         None => {
             let projects_fmt = format_projects(&projects, false);
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<PROJECT>")
                 .with_prompt("Select a project")
                 .items(&projects_fmt)
                 .default(if let Some(current) = state.ctx.current_project() {
@@ -16,7 +16,7 @@ pub struct SingleProjectResponse {
     pub project: Project,
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub enum ProjectTier {
     #[default]
     #[serde(rename = "free")]
@@ -25,7 +25,7 @@ pub enum ProjectTier {
     Paid,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Project {
     pub id: String,
     pub name: String,
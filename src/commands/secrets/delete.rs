@@ -4,7 +4,9 @@ use serde_json::Value;
 
 use crate::commands::secrets::types::Secrets;
 use crate::commands::secrets::utils::validate_name;
+use crate::errors::HopError;
 use crate::state::State;
+use crate::utils::prompt;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Delete a secret")]
@@ -40,7 +42,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
                 .map(|s| format!(" {} ({})", s.name, s.id))
                 .collect::<Vec<_>>();
 
-            let idx = dialoguer::Select::new()
+            let idx = prompt::Select::new("<NAME>")
                 .with_prompt("Select a secret")
                 .items(&secrets_fmt)
                 .default(0)
@@ -51,14 +53,14 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     };
 
     if !options.force
-        && !dialoguer::Confirm::new()
+        && !prompt::Confirm::new("--force")
             .with_prompt(format!(
                 "Are you sure you want to delete secret `{secret_name}`?"
             ))
             .interact_opt()?
             .unwrap_or(false)
     {
-        bail!("Aborted");
+        bail!(HopError::cancelled());
     }
 
     state
@@ -1,8 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 
-use crate::commands::secrets::types::Secrets;
-use crate::commands::secrets::utils::format_secrets;
+use crate::commands::secrets::utils::{format_secrets, get_secrets};
 use crate::state::State;
 
 #[derive(Debug, Parser)]
@@ -16,14 +15,11 @@ pub struct Options {
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project_id = state.ctx.current_project_error()?.id;
 
-    let secrets = state
-        .http
-        .request::<Secrets>("GET", &format!("/projects/{project_id}/secrets"), None)
-        .await?
-        .unwrap()
-        .secrets;
+    let secrets = get_secrets(&state.http, &project_id).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&secrets)? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = secrets
             .iter()
             .map(|d| d.id.as_str())
@@ -1,10 +1,20 @@
 use std::io::Write;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use regex::Regex;
 use tabwriter::TabWriter;
 
-use super::types::Secret;
+use super::types::{Secret, Secrets};
+use crate::state::http::HttpClient;
+
+pub async fn get_secrets(http: &HttpClient, project_id: &str) -> Result<Vec<Secret>> {
+    let response = http
+        .request::<Secrets>("GET", &format!("/projects/{project_id}/secrets"), None)
+        .await?
+        .ok_or_else(|| anyhow!("Failed to parse response"))?;
+
+    Ok(response.secrets)
+}
 
 pub fn validate_name(name: &str) -> Result<()> {
     let regex = regex::Regex::new(r"(?i)^[a-z0-9_]{1,64}$").unwrap();
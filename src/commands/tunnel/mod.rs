@@ -15,7 +15,8 @@ use crate::commands::gateways::util::{format_gateways, get_all_gateways};
 use crate::commands::ignite::types::Deployment;
 use crate::commands::tunnel::utils::{add_entry_to_hosts, remove_entry_from_hosts};
 use crate::state::State;
-use crate::utils::urlify;
+use crate::utils::resolve::split_reference;
+use crate::utils::{prompt, urlify};
 
 // TLS Socker Uri
 const TONNERU_URI: &str = "tonneru.hop.io";
@@ -27,35 +28,40 @@ const CONTAINER_PREFIX: &str = "container_";
 #[clap(about = "Access your application via a tunnel")]
 #[group(skip)]
 pub struct Options {
-    #[clap(help = "Resource to tunnel to, can be a deployment name, ID")]
+    #[clap(
+        help = "Resource to tunnel to, a deployment name or ID, a container ID or `<deployment>/<index>` of a container"
+    )]
     pub deployment: Option<String>,
     #[clap(long, help = "Publish a container's port(s) to the host", value_parser = parse_publish)]
     pub publish: Option<(IpAddr, u16, u16)>,
     #[clap(long, help = "Add an entry to your hosts file with the tunnel domain")]
     pub hosts: bool,
+    #[clap(
+        long,
+        requires = "hosts",
+        help = "Internal Gateway whose domain is added to the hosts file, by name or ID"
+    )]
+    pub gateway: Option<String>,
 }
 
 pub async fn handle(options: &Options, state: State) -> Result<()> {
-    // check if the options.deployment starts with container_ else use the
-    // state.get_deployment_by_opt_id_or_name
-    let resource = if options
-        .deployment
-        .clone()
-        .map(|d| d.starts_with(CONTAINER_PREFIX))
-        .unwrap_or(false)
-    {
-        let container = options.deployment.clone().unwrap();
-
-        Deployment {
-            name: container.clone(),
-            id: container,
-            container_count: 1,
-            ..Default::default()
+    // a container can be passed by its ID or as `<deployment>/<index>`,
+    // anything else is resolved as a deployment
+    let resource = match options.deployment.as_deref() {
+        Some(reference)
+            if reference.starts_with(CONTAINER_PREFIX) || split_reference(reference).is_some() =>
+        {
+            let container = state.get_container_by_name_or_id(reference).await?;
+
+            Deployment {
+                name: container.id.clone(),
+                id: container.id,
+                container_count: 1,
+                ..Default::default()
+            }
         }
-    } else {
-        state
-            .get_deployment_by_opt_name_or_id(options.deployment.as_deref())
-            .await?
+
+        reference => state.get_deployment_by_opt_name_or_id(reference).await?,
     };
 
     ensure!(
@@ -66,7 +72,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
     let (ip_address, local_port, remote_port) = if let Some(publish_values) = options.publish {
         publish_values
     } else {
-        let local_address = dialoguer::Input::<IpAddr>::new()
+        let local_address = prompt::Input::<IpAddr>::new("--publish")
             .with_prompt("Local IP address to bind to")
             .default(IpAddr::from([127, 0, 0, 1]))
             .interact()?;
@@ -98,7 +104,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
             let idx = if ports.len() == 1 {
                 0
             } else {
-                dialoguer::Select::new()
+                prompt::Select::new("--publish")
                     .with_prompt("Select a local port")
                     .items(&ports)
                     .default(0)
@@ -106,7 +112,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
             };
 
             if idx == ports.len() - 1 {
-                dialoguer::Input::<u16>::new()
+                prompt::Input::<u16>::new("--publish")
                     .with_prompt("Local port number")
                     .interact()?
             } else {
@@ -118,7 +124,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
             let idx = if ports.len() == 1 {
                 0
             } else {
-                dialoguer::Select::new()
+                prompt::Select::new("--publish")
                     .with_prompt("Select the remote port")
                     .items(&ports)
                     .default(0)
@@ -126,7 +132,7 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
             };
 
             if idx == ports.len() - 1 {
-                dialoguer::Input::<u16>::new()
+                prompt::Input::<u16>::new("--publish")
                     .with_prompt("Remote port number")
                     .interact()?
             } else {
@@ -158,15 +164,26 @@ pub async fn handle(options: &Options, state: State) -> Result<()> {
             vec![]
         };
 
+        if let Some(gateway) = &options.gateway {
+            gateways.retain(|g| g.id == *gateway || g.name.as_deref() == Some(gateway));
+
+            ensure!(
+                !gateways.is_empty(),
+                "No internal Gateway `{gateway}` found on {}",
+                resource.name
+            );
+        }
+
         match &mut gateways[..] {
             [] => format!("{}.{DOMAIN_SUFFIX}", resource.name),
 
             // safe to unwrap because we filter out non-internal gateways
             [single] => single.internal_domain.take().unwrap(),
+
             _ => {
                 let gateways_fmt = format_gateways(&gateways, false);
 
-                let gateway = dialoguer::Select::new()
+                let gateway = prompt::Select::new("--gateway")
                     .with_prompt("Select an internal gateway that best suits your needs")
                     .items(&gateways_fmt)
                     .default(0)
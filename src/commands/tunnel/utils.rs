@@ -12,15 +12,12 @@ use tokio::net::TcpStream;
 #[cfg(windows)]
 use tokio_native_tls::{native_tls::TlsConnector, TlsStream};
 #[cfg(not(windows))]
-use tokio_rustls::{
-    client::TlsStream,
-    rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore},
-};
+use tokio_rustls::{client::TlsStream, rustls::ClientConfig};
 
 use super::types::TonneruPacket;
 use super::{TONNERU_PORT, TONNERU_URI};
 use crate::commands::update::util::execute_commands;
-use crate::utils::is_writable;
+use crate::utils::{is_writable, network};
 
 #[derive(Clone)]
 pub struct TonneruSocket {
@@ -38,27 +35,10 @@ type TlsSocket = TlsStream<TcpStream>;
 impl TonneruSocket {
     pub fn new(token: &str, resource_id: &str, port: u16) -> Result<Self> {
         #[cfg(windows)]
-        let config = native_tls::TlsConnector::new()?;
+        let config = network::tls_connector()?;
 
         #[cfg(not(windows))]
-        let config = {
-            // ref: https://github.com/rustls/hyper-rustls/blob/fcb72be6e3b0e060bfe5bc183a67c16ea56e7132/src/config.rs#L55-L69
-            let mut roots = RootCertStore::empty();
-            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
-                OwnedTrustAnchor::from_subject_spki_name_constraints(
-                    ta.subject,
-                    ta.spki,
-                    ta.name_constraints,
-                )
-            }));
-
-            Arc::new(
-                ClientConfig::builder()
-                    .with_safe_defaults()
-                    .with_root_certificates(roots)
-                    .with_no_client_auth(),
-            )
-        };
+        let config = network::tls_config()?;
 
         Ok(Self {
             token: token.to_string(),
@@ -73,7 +53,7 @@ impl TonneruSocket {
         use tokio_rustls::rustls::ServerName;
         use tokio_rustls::TlsConnector;
 
-        let remote = TcpStream::connect(format!("{TONNERU_URI}:{TONNERU_PORT}")).await?;
+        let remote = network::connect(TONNERU_URI, TONNERU_PORT).await?;
 
         log::debug!("Connected to {TONNERU_URI}:{TONNERU_PORT}");
 
@@ -91,7 +71,7 @@ impl TonneruSocket {
     async fn open_socket(&self) -> Result<TlsSocket> {
         use tokio_native_tls::TlsConnector;
 
-        let remote = TcpStream::connect(format!("{TONNERU_URI}:{TONNERU_PORT}")).await?;
+        let remote = network::connect(TONNERU_URI, TONNERU_PORT).await?;
 
         log::debug!("TLS connection open to {TONNERU_URI}:{TONNERU_PORT}");
 
@@ -0,0 +1,89 @@
+use std::env::current_dir;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+
+use crate::store::hopfile::{self, HopFile};
+
+#[derive(Debug, Parser)]
+#[clap(
+    about = "Check a hopfile for errors",
+    long_about = "Check a hopfile for errors\n\nThe hopfile and every one of its environments are checked, or only the environment selected with `--environment`. Errors in the YAML or JSON are reported with their line and column."
+)]
+#[group(skip)]
+pub struct Options {
+    #[clap(
+        name = "path",
+        help = "Hopfile or directory with the hopfile, defaults to current directory"
+    )]
+    pub path: Option<PathBuf>,
+
+    #[clap(long, help = "Print the JSON Schema of hopfiles instead")]
+    pub schema: bool,
+}
+
+pub async fn handle(options: Options) -> Result<()> {
+    if options.schema {
+        println!("{}", serde_json::to_string_pretty(&HopFile::json_schema())?);
+
+        return Ok(());
+    }
+
+    let mut path = current_dir()?;
+
+    if let Some(option) = options.path {
+        path = path.join(option);
+    }
+
+    let path = HopFile::find_file(path)?;
+    let content = HopFile::read(&path).await?;
+    let variables = HopFile::variables(&path).await?;
+
+    let raw = HopFile::parse_resolved::<HopFile>(&path, &content, &variables)?;
+
+    let environments = match hopfile::environment() {
+        Some(environment) => vec![Some(environment)],
+
+        None => {
+            // a hopfile can leave everything to its environments
+            let complete = raw.deployment_ref().is_some() || !raw.services.is_empty();
+
+            (complete || raw.environments.is_empty())
+                .then_some(None)
+                .into_iter()
+                .chain(raw.environments.keys().cloned().map(Some))
+                .collect()
+        }
+    };
+
+    let mut errors = 0;
+
+    for environment in &environments {
+        let name = environment
+            .as_ref()
+            .map(|name| format!("The `{name}` environment of {}", path.display()))
+            .unwrap_or_else(|| path.display().to_string());
+
+        match HopFile::deserialize_environment(
+            path.clone(),
+            &content,
+            &variables,
+            environment.as_deref(),
+        ) {
+            Ok(_) => log::info!("{name} is valid"),
+
+            Err(error) => {
+                log::error!("{error}");
+
+                errors += 1;
+            }
+        }
+    }
+
+    if errors > 0 {
+        bail!("{} is invalid", path.display());
+    }
+
+    Ok(())
+}
@@ -45,6 +45,12 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         );
     }
 
+    if let Some(output) = state.output.serialize(&files_map)? {
+        println!("{output}");
+
+        return Ok(());
+    }
+
     let is_mult_checked = files_map.len() > 1;
     let mut is_first_element = true;
 
@@ -3,9 +3,9 @@ use clap::Parser;
 use hop::webhooks::types::{PossibleEvents, EVENT_NAMES};
 
 use super::utils::string_to_event;
-use crate::commands::webhooks::utils::get_formatted_events;
+use crate::commands::webhooks::utils::{create_webhook, get_formatted_events};
 use crate::state::State;
-use crate::utils::urlify;
+use crate::utils::{prompt, urlify};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Create a new webhook")]
@@ -23,7 +23,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let url = if let Some(url) = options.url {
         url
     } else {
-        dialoguer::Input::new()
+        prompt::Input::new("--url")
             .with_prompt("Webhook URL")
             .interact_text()?
     };
@@ -32,7 +32,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         options.events
     } else {
         let dialoguer_events = loop {
-            let test = dialoguer::MultiSelect::new()
+            let test = prompt::MultiSelect::new("--events")
                 .with_prompt("Select events")
                 .items(&get_formatted_events()?)
                 .interact()?;
@@ -50,11 +50,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .collect()
     };
 
-    let webhook = state
-        .hop
-        .webhooks
-        .create(&project.id, &url, &events)
-        .await?;
+    let webhook = create_webhook(&state.http, &project.id, &url, &events).await?;
 
     log::info!("Webhook successfully created. ID: {}\n", webhook.id);
     log::info!("This is your webhook's secret, this is how you will authenticate traffic coming to your endpoint");
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 
-use super::utils::format_webhooks;
+use super::utils::{format_webhooks, get_all_webhooks, serialize_webhooks};
 use crate::state::State;
 
 #[derive(Debug, Parser)]
@@ -15,9 +15,11 @@ pub struct Options {
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project = state.ctx.current_project_error()?;
 
-    let webhooks = state.hop.webhooks.get_all(&project.id).await?;
+    let webhooks = get_all_webhooks(&state.http, &project.id).await?;
 
-    if options.quiet {
+    if let Some(output) = state.output.serialize(&serialize_webhooks(&webhooks))? {
+        println!("{output}");
+    } else if options.quiet {
         let ids = webhooks
             .iter()
             .map(|d| d.id.as_str())
@@ -1,31 +1,32 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Parser;
 
-use crate::commands::webhooks::utils::format_webhooks;
+use crate::commands::webhooks::utils::{
+    format_webhooks, get_all_webhooks, regenerate_webhook_secret,
+};
 use crate::state::State;
-use crate::utils::urlify;
+use crate::utils::resolve::find_one;
+use crate::utils::{prompt, urlify};
 
 #[derive(Debug, Parser)]
 #[clap(about = "Regenerate a webhook secret")]
 #[group(skip)]
 pub struct Options {
-    #[clap(short, long, help = "The id of the webhook")]
+    #[clap(short, long, help = "ID, ID prefix or URL of the webhook")]
     pub id: Option<String>,
 }
 
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project = state.ctx.current_project_error()?;
 
-    let all = state.hop.webhooks.get_all(&project.id).await?;
+    let all = get_all_webhooks(&state.http, &project.id).await?;
 
-    let webhook = if let Some(id) = options.id {
-        all.into_iter()
-            .find(|webhook| webhook.id == id)
-            .context("Webhook not found")?
+    let webhook = if let Some(reference) = options.id {
+        find_one(&all, &reference)?.clone()
     } else {
         let formatted_webhooks = format_webhooks(&all, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--id")
             .with_prompt("Select a webhook to update")
             .items(&formatted_webhooks)
             .default(0)
@@ -34,11 +35,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         all[idx].clone()
     };
 
-    let token = state
-        .hop
-        .webhooks
-        .regenerate_secret(&project.id, &webhook.id)
-        .await?;
+    let token = regenerate_webhook_secret(&state.http, &project.id, &webhook.id).await?;
 
     log::info!("This is your webhook's secret, this is how you will authenticate traffic coming to your endpoint");
     log::info!("Webhook Header: {}", urlify("X-Hop-Hooks-Signature"));
@@ -1,16 +1,20 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Parser;
 use hop::webhooks::types::{PossibleEvents, EVENT_NAMES};
 
 use super::utils::string_to_event;
-use crate::commands::webhooks::utils::{format_webhooks, get_formatted_events};
+use crate::commands::webhooks::utils::{
+    format_webhooks, get_all_webhooks, get_formatted_events, update_webhook,
+};
 use crate::state::State;
+use crate::utils::prompt;
+use crate::utils::resolve::find_one;
 
 #[derive(Debug, Parser)]
 #[clap(about = "Update a webhook")]
 #[group(skip)]
 pub struct Options {
-    #[clap(short, long, help = "The id of the webhook")]
+    #[clap(short, long, help = "ID, ID prefix or URL of the webhook")]
     pub id: Option<String>,
     #[clap(short, long, help = "The url to send the webhook to")]
     pub url: Option<String>,
@@ -21,16 +25,14 @@ pub struct Options {
 pub async fn handle(options: Options, state: State) -> Result<()> {
     let project = state.ctx.current_project_error()?;
 
-    let all = state.hop.webhooks.get_all(&project.id).await?;
+    let all = get_all_webhooks(&state.http, &project.id).await?;
 
-    let old = if let Some(id) = options.id {
-        all.into_iter()
-            .find(|webhook| webhook.id == id)
-            .context("Webhook not found")?
+    let old = if let Some(reference) = options.id {
+        find_one(&all, &reference)?.clone()
     } else {
         let formatted_webhooks = format_webhooks(&all, false);
 
-        let idx = dialoguer::Select::new()
+        let idx = prompt::Select::new("--id")
             .with_prompt("Select a webhook")
             .items(&formatted_webhooks)
             .default(0)
@@ -42,7 +44,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
     let url = if let Some(url) = options.url {
         url
     } else {
-        dialoguer::Input::new()
+        prompt::Input::new("--url")
             .with_prompt("Webhook URL")
             .default(old.webhook_url)
             .interact_text()?
@@ -52,7 +54,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
         options.events
     } else {
         let dialoguer_events = loop {
-            let test = dialoguer::MultiSelect::new()
+            let test = prompt::MultiSelect::new("--events")
                 .with_prompt("Select events")
                 .items(&get_formatted_events()?)
                 .defaults(&EVENT_NAMES.map(|(event, _)| old.events.contains(&event)))
@@ -71,11 +73,7 @@ pub async fn handle(options: Options, state: State) -> Result<()> {
             .collect()
     };
 
-    let webhook = state
-        .hop
-        .webhooks
-        .patch(&project.id, &old.id, &url, &events)
-        .await?;
+    let webhook = update_webhook(&state.http, &project.id, &old.id, &url, &events).await?;
 
     log::info!("Webhook successfully created. ID: {}", webhook.id);
 
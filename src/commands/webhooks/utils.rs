@@ -1,9 +1,118 @@
 use std::io::Write;
 
-use anyhow::Result;
-use hop::webhooks::types::{PossibleEvents, Webhook, EVENT_CATEGORIES, EVENT_NAMES};
+use anyhow::{anyhow, Result};
+use hop::webhooks::types::{PossibleEvents, Webhook, WebhookSecret, EVENT_CATEGORIES, EVENT_NAMES};
+use serde::Deserialize;
+use serde_json::{json, Value};
 use tabwriter::TabWriter;
 
+use crate::state::http::HttpClient;
+use crate::utils::resolve::Resolvable;
+
+#[derive(Debug, Deserialize)]
+struct SingleWebhook {
+    webhook: Webhook,
+}
+
+#[derive(Debug, Deserialize)]
+struct MultipleWebhooks {
+    webhooks: Vec<Webhook>,
+}
+
+impl Resolvable for Webhook {
+    const KIND: &'static str = "webhook";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.webhook_url)
+    }
+}
+
+// webhooks go through our own client rather than the SDK, so they use the
+// same proxy, certificates, retries and timeout as every other request
+pub async fn get_all_webhooks(http: &HttpClient, project_id: &str) -> Result<Vec<Webhook>> {
+    let response = http
+        .request::<MultipleWebhooks>("GET", &format!("/projects/{project_id}/webhooks"), None)
+        .await?
+        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+
+    Ok(response.webhooks)
+}
+
+pub async fn create_webhook(
+    http: &HttpClient,
+    project_id: &str,
+    url: &str,
+    events: &[PossibleEvents],
+) -> Result<Webhook> {
+    let response = http
+        .request::<SingleWebhook>(
+            "POST",
+            &format!("/projects/{project_id}/webhooks"),
+            Some((
+                serde_json::to_vec(&json!({ "webhook_url": url, "events": events }))?.into(),
+                "application/json",
+            )),
+        )
+        .await?
+        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+
+    Ok(response.webhook)
+}
+
+pub async fn update_webhook(
+    http: &HttpClient,
+    project_id: &str,
+    webhook_id: &str,
+    url: &str,
+    events: &[PossibleEvents],
+) -> Result<Webhook> {
+    let response = http
+        .request::<SingleWebhook>(
+            "PATCH",
+            &format!("/projects/{project_id}/webhooks/{webhook_id}"),
+            Some((
+                serde_json::to_vec(&json!({ "webhook_url": url, "events": events }))?.into(),
+                "application/json",
+            )),
+        )
+        .await?
+        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+
+    Ok(response.webhook)
+}
+
+pub async fn regenerate_webhook_secret(
+    http: &HttpClient,
+    project_id: &str,
+    webhook_id: &str,
+) -> Result<String> {
+    let response = http
+        .request::<WebhookSecret>(
+            "POST",
+            &format!("/projects/{project_id}/webhooks/{webhook_id}/regenerate_secret"),
+            None,
+        )
+        .await?
+        .ok_or_else(|| anyhow!("Error while parsing response"))?;
+
+    Ok(response.secret)
+}
+
+pub async fn delete_webhook(http: &HttpClient, project_id: &str, webhook_id: &str) -> Result<()> {
+    http.request::<Value>(
+        "DELETE",
+        &format!("/projects/{project_id}/webhooks/{webhook_id}"),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
 pub fn format_webhooks(webhooks: &[Webhook], title: bool) -> Vec<String> {
     let mut tw = TabWriter::new(vec![]);
 
@@ -29,6 +138,23 @@ pub fn format_webhooks(webhooks: &[Webhook], title: bool) -> Vec<String> {
         .collect()
 }
 
+// the webhook type from the sdk is not serializable, so map it manually
+pub fn serialize_webhooks(webhooks: &[Webhook]) -> Vec<Value> {
+    webhooks
+        .iter()
+        .map(|webhook| {
+            json!({
+                "id": webhook.id,
+                "project_id": webhook.project_id,
+                "webhook_url": webhook.webhook_url,
+                "events": webhook.events,
+                "type": webhook.type_,
+                "created_at": webhook.created_at,
+            })
+        })
+        .collect()
+}
+
 pub fn string_to_event(string: &str) -> Result<PossibleEvents> {
     serde_json::from_str(string).map_err(|e| e.into())
 }
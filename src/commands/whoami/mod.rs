@@ -16,6 +16,12 @@ pub fn handle(_options: &Options, state: State) -> Result<()> {
         .clone()
         .ok_or_else(|| anyhow!("You are not logged in"))?;
 
+    if let Some(output) = state.output.serialize(&authorized)? {
+        println!("{output}");
+
+        return Ok(());
+    }
+
     log::info!(
         "You are logged in as `{}` ({})",
         authorized.name,
@@ -8,6 +8,9 @@ pub const EXEC_NAME: &str = "hop";
 pub const EXEC_NAME: &str = "hop.exe";
 pub const LEAP_PROJECT: &str = "project_MzA0MDgwOTQ2MDEwODQ5NzQ";
 
+/// default of `deploy.max_upload_size`, 1GB
+pub const DEFAULT_MAX_UPLOAD_SIZE: u64 = 1024 * 1024 * 1024;
+
 #[cfg(windows)]
 pub const DEFAULT_EDITOR: &str = "notepad.exe";
 #[cfg(not(windows))]
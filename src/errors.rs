@@ -0,0 +1,217 @@
+//! Typed errors of the CLI, each kind maps to a documented process exit code:
+//!
+//! | Code | Kind         | Meaning                                          |
+//! |------|--------------|--------------------------------------------------|
+//! | 0    |              | Success                                          |
+//! | 1    | `other`      | Any other error                                  |
+//! | 2    | `usage`      | Invalid or missing arguments                     |
+//! | 3    | `auth`       | Not logged in, invalid token or missing access   |
+//! | 4    | `not_found`  | The resource does not exist                      |
+//! | 5    | `conflict`   | The resource already exists or is in use         |
+//! | 6    | `quota`      | A quota or rate limit was exceeded               |
+//! | 7    | `validation` | The API rejected the request body                |
+//! | 8    | `network`    | The API could not be reached                     |
+//! | 9    | `server`     | The API failed to handle the request             |
+//! | 130  | `cancelled`  | The user cancelled the command                   |
+
+use std::fmt::Display;
+
+use hyper::StatusCode;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Other,
+    Usage,
+    Auth,
+    NotFound,
+    Conflict,
+    Quota,
+    Validation,
+    Network,
+    Server,
+    Cancelled,
+}
+
+impl ErrorKind {
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::Usage => 2,
+            Self::Auth => 3,
+            Self::NotFound => 4,
+            Self::Conflict => 5,
+            Self::Quota => 6,
+            Self::Validation => 7,
+            Self::Network => 8,
+            Self::Server => 9,
+            Self::Cancelled => 130,
+        }
+    }
+
+    /// Classifies a failed API response, the error code
+    /// of the API takes precedence over the HTTP status
+    pub fn from_response(status: StatusCode, code: Option<&str>) -> Self {
+        if code.is_some_and(|code| code.contains("quota") || code.contains("limit")) {
+            return Self::Quota;
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::PAYMENT_REQUIRED | StatusCode::TOO_MANY_REQUESTS => Self::Quota,
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation,
+            status if status.is_server_error() => Self::Server,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HopError {
+    pub kind: ErrorKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Error code returned by the API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl std::error::Error for HopError {}
+
+impl Display for HopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl HopError {
+    pub fn new(kind: ErrorKind, message: impl Display) -> Self {
+        Self {
+            kind,
+            status: None,
+            code: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn api(status: StatusCode, code: Option<String>, message: impl Display) -> Self {
+        Self {
+            kind: ErrorKind::from_response(status, code.as_deref()),
+            status: Some(status.as_u16()),
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self::new(ErrorKind::Cancelled, "Aborted")
+    }
+
+    /// Finds the most specific kind of an error by looking through its causes
+    pub fn kind_of(error: &anyhow::Error) -> ErrorKind {
+        error
+            .chain()
+            .find_map(|cause| {
+                if let Some(error) = cause.downcast_ref::<Self>() {
+                    Some(error.kind)
+                } else {
+                    cause
+                        .downcast_ref::<reqwest::Error>()
+                        .filter(|error| error.is_connect() || error.is_timeout())
+                        .map(|_| ErrorKind::Network)
+                }
+            })
+            .unwrap_or(ErrorKind::Other)
+    }
+}
+
+/// Machine readable representation of an error, used with `--output json`
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub error: HopError,
+    pub exit_code: i32,
+}
+
+impl From<&anyhow::Error> for ErrorOutput {
+    fn from(error: &anyhow::Error) -> Self {
+        let mut typed = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<HopError>())
+            .cloned()
+            .unwrap_or_else(|| HopError::new(HopError::kind_of(error), ""));
+
+        // keep the context added on top of the typed error
+        typed.message = error.to_string();
+
+        Self {
+            exit_code: typed.kind.exit_code(),
+            error: typed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Context;
+
+    use super::*;
+
+    #[test]
+    fn test_kind_from_response() {
+        assert_eq!(
+            ErrorKind::from_response(StatusCode::UNAUTHORIZED, None),
+            ErrorKind::Auth
+        );
+        assert_eq!(
+            ErrorKind::from_response(StatusCode::NOT_FOUND, Some("deployment_not_found")),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            ErrorKind::from_response(StatusCode::BAD_REQUEST, Some("quota_exceeded")),
+            ErrorKind::Quota
+        );
+        assert_eq!(
+            ErrorKind::from_response(StatusCode::BAD_GATEWAY, None),
+            ErrorKind::Server
+        );
+        assert_eq!(
+            ErrorKind::from_response(StatusCode::IM_A_TEAPOT, None),
+            ErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_error_output_keeps_context() {
+        let error = Err::<(), _>(HopError::api(
+            StatusCode::CONFLICT,
+            Some("name_taken".to_string()),
+            "Name is already taken",
+        ))
+        .context("Failed to create deployment")
+        .unwrap_err();
+
+        let output = ErrorOutput::from(&error);
+
+        assert_eq!(output.exit_code, 5);
+        assert_eq!(output.error.kind, ErrorKind::Conflict);
+        assert_eq!(output.error.status, Some(409));
+        assert_eq!(output.error.code.as_deref(), Some("name_taken"));
+        assert_eq!(output.error.message, "Failed to create deployment");
+    }
+
+    #[test]
+    fn test_untyped_error_kind() {
+        assert_eq!(
+            HopError::kind_of(&anyhow::anyhow!("something went wrong")),
+            ErrorKind::Other
+        );
+        assert_eq!(
+            HopError::kind_of(&HopError::cancelled().into()),
+            ErrorKind::Cancelled
+        );
+    }
+}
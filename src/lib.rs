@@ -1,15 +1,25 @@
 pub(crate) mod commands;
 pub(crate) mod config;
+pub(crate) mod errors;
 pub(crate) mod state;
 pub(crate) mod store;
 pub(crate) mod utils;
 
-use anyhow::Result;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use clap::Parser;
 use commands::update::version_notice;
 use commands::{handle_command, Commands};
 use config::{ARCH, PLATFORM, VERSION};
+use errors::ErrorOutput;
+use state::http::HttpOptions;
 use state::{State, StateOptions};
+use store::config::Config;
+use utils::logging::{LogFormat, LogOptions};
+use utils::output::OutputFormat;
 
 #[derive(Debug, Parser)]
 #[clap(
@@ -30,8 +40,69 @@ pub struct CLI {
     )]
     pub project: Option<String>,
 
+    #[clap(
+        long,
+        help = "Context to use for this command, can also be set with `HOP_CONTEXT`",
+        global = true
+    )]
+    pub context: Option<String>,
+
+    #[clap(
+        long,
+        help = "Environment of the hopfile to use, like `staging`, can also be set with `HOP_ENV`",
+        global = true
+    )]
+    pub environment: Option<String>,
+
     #[clap(short = 'D', long, help = "Enable debug mode", global = true)]
     pub debug: bool,
+
+    #[clap(
+        short,
+        long,
+        help = "Output format of list and inspect commands, one of `table`, `json` or `yaml` [default: table]",
+        global = true
+    )]
+    pub output: Option<OutputFormat>,
+
+    #[clap(
+        long,
+        help = "Fail instead of prompting for missing arguments, enabled by default in CI or when stdin is not a terminal",
+        global = true
+    )]
+    pub no_input: bool,
+
+    #[clap(
+        long,
+        help = "Amount of times failed API requests are retried",
+        default_value = "3",
+        global = true
+    )]
+    pub api_retries: u32,
+
+    #[clap(long, help = "Timeout of API requests in seconds", global = true)]
+    pub api_timeout: Option<u64>,
+
+    #[clap(
+        long,
+        help = "PEM file with extra CA certificates to trust, e.g. of a corporate proxy",
+        global = true
+    )]
+    pub ca_cert: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Format of the logs, one of `text` or `json`, can also be set with `HOP_LOG_FORMAT` [default: text]",
+        global = true
+    )]
+    pub log_format: Option<LogFormat>,
+
+    #[clap(
+        long,
+        help = "Append all logs, including debug ones, to this file",
+        global = true
+    )]
+    pub log_file: Option<PathBuf>,
 }
 
 pub async fn run() -> Result<()> {
@@ -41,17 +112,112 @@ pub async fn run() -> Result<()> {
     // setup panic hook
     utils::set_hook();
 
-    utils::logs(cli.debug);
+    let config = Config::load().await;
+
+    let log_format = match (cli.log_format, std::env::var("HOP_LOG_FORMAT")) {
+        (Some(format), _) => Ok(format),
+        (None, Ok(format)) => format
+            .parse::<LogFormat>()
+            .context("Invalid value of `HOP_LOG_FORMAT`"),
+        (None, Err(_)) => Ok(config
+            .as_ref()
+            .ok()
+            .and_then(|config| config.log_format)
+            .unwrap_or_default()),
+    };
+
+    let logging = utils::logging::setup(LogOptions {
+        verbose: cli.debug,
+        color: config.as_ref().map_or(true, Config::colors_enabled),
+        format: log_format.as_ref().copied().unwrap_or_default(),
+        file: cli.log_file.clone(),
+    });
+
+    let output = cli
+        .output
+        .or_else(|| config.as_ref().ok().and_then(|config| config.output))
+        .unwrap_or_default();
+
+    let config = match config {
+        // the config commands are how a broken config gets fixed
+        Err(error) if logging.is_ok() && matches!(cli.commands, Commands::Config(_)) => {
+            log::warn!("{error:#}, using the defaults");
+
+            Ok(Config::default())
+        }
+
+        Ok(config) => {
+            if !config.ignored_keys.is_empty() {
+                log::warn!(
+                    "Ignoring {} in the project config, set `trust_project_config = true` in the user config to allow them",
+                    config
+                        .ignored_keys
+                        .iter()
+                        .map(|key| format!("`{key}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+
+            Ok(config)
+        }
+
+        config => config,
+    };
+
+    let result = match (config, log_format, logging) {
+        (Ok(config), Ok(_), Ok(())) => execute(cli, config, output).await,
+        (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => Err(error),
+    };
+
+    if let Err(error) = result {
+        std::process::exit(report_error(&error, output));
+    }
+
+    utils::clean_term();
+
+    Ok(())
+}
 
+async fn execute(cli: CLI, config: Config, output: OutputFormat) -> Result<()> {
     // in the debug mode, print the version and arch for easier debugging
     log::debug!("Hop-CLI v{VERSION} build for {ARCH}-{PLATFORM}");
 
     utils::sudo::fix().await?;
 
+    let no_input = cli.no_input || config.no_input.unwrap_or(false);
+
+    if let Some(ca_cert) = cli.ca_cert.as_ref().or(config.ca_cert.as_ref()) {
+        utils::network::set_ca_cert(ca_cert)?;
+    }
+
+    store::hopfile::set_environment(
+        cli.environment
+            .or_else(|| std::env::var("HOP_ENV").ok())
+            .filter(|environment| !environment.is_empty()),
+    );
+
+    // before the state is created, opening the credential store can prompt.
+    // completions run inside the shell, a prompt there would hang it
+    utils::prompt::set_no_input(
+        no_input
+            || matches!(cli.commands, Commands::DynamicCompletions(_))
+            || State::check_if_ci()
+            || !std::io::stdin().is_terminal(),
+    );
+
     let state = State::new(StateOptions {
+        override_context: cli.context.or_else(|| std::env::var("HOP_CONTEXT").ok()),
         override_project: std::env::var("PROJECT_ID").ok().or(cli.project),
         override_token: std::env::var("TOKEN").ok(),
         debug: cli.debug,
+        output,
+        http: HttpOptions {
+            max_retries: cli.api_retries,
+            timeout: cli.api_timeout.map(Duration::from_secs),
+            ..Default::default()
+        },
+        config,
     })
     .await?;
 
@@ -61,7 +227,7 @@ pub async fn run() -> Result<()> {
 
         // do not show the notice if we are in completions mode
         // since it could break the shell
-        Commands::Completions(_) => {}
+        Commands::Completions(_) | Commands::DynamicCompletions(_) => {}
 
         // only show the notice if we are not in debug mode or in CI
         _ if cfg!(debug_assertions) || state.is_ci => {}
@@ -78,15 +244,26 @@ pub async fn run() -> Result<()> {
         }
     };
 
-    if let Err(error) = handle_command(cli.commands, state).await {
-        log::error!("{error}");
-        log::debug!("{error:#?}");
-        std::process::exit(1);
-    }
+    handle_command(cli.commands, state).await
+}
 
-    utils::clean_term();
+/// Prints the error and returns the exit code matching its kind,
+/// errors are printed as JSON to stderr when the output is JSON
+fn report_error(error: &anyhow::Error, output: OutputFormat) -> i32 {
+    let report = ErrorOutput::from(error);
 
-    Ok(())
+    log::debug!("{error:#?}");
+
+    match output {
+        OutputFormat::Json => eprintln!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_else(|_| error.to_string())
+        ),
+
+        _ => log::error!("{error}"),
+    }
+
+    report.exit_code
 }
 
 #[cfg(test)]
@@ -99,4 +276,31 @@ mod test {
 
         CLI::command().debug_assert();
     }
+
+    #[test]
+    fn test_global_flags_do_not_clash() {
+        use clap::Parser;
+
+        use super::*;
+
+        // subcommands have their own `--timeout` and `--max-retries`
+        let cli = CLI::try_parse_from([
+            "hop",
+            "ignite",
+            "health",
+            "create",
+            "api",
+            "--timeout",
+            "5",
+            "--max-retries",
+            "2",
+            "--api-timeout",
+            "30",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.api_timeout, Some(30));
+        assert_eq!(cli.api_retries, 3);
+        assert!(format!("{:?}", cli.commands).contains("max_retries: Some(2)"));
+    }
 }
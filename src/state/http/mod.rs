@@ -1,25 +1,63 @@
+pub mod pagination;
 mod types;
 
-use anyhow::{anyhow, bail, Result};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use hyper::StatusCode;
-use reqwest::header::HeaderMap;
-use reqwest::Client as AsyncClient;
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client as AsyncClient, Method};
 
 use self::types::{Base, ErrorResponse};
 use crate::config::VERSION;
+use crate::errors::{ErrorKind, HopError};
+use crate::utils::network;
 
 const HOP_API_BASE_URL: &str = "https://api.hop.io/v1";
 
+/// Upper bound for a single wait between retries
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// Amount of times a failed request is retried
+    pub max_retries: u32,
+    /// Base delay of the exponential backoff
+    pub backoff: Duration,
+    pub timeout: Option<Duration>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+            timeout: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     pub client: AsyncClient,
     pub base_url: String,
     pub headers: HeaderMap,
     pub ua: String,
+    pub options: HttpOptions,
 }
 
 impl HttpClient {
     pub fn new(token: Option<String>, api_url: Option<String>) -> Self {
+        Self::with_options(token, api_url, HttpOptions::default())
+    }
+
+    pub fn with_options(
+        token: Option<String>,
+        api_url: Option<String>,
+        options: HttpOptions,
+    ) -> Self {
         let mut headers = HeaderMap::new();
 
         headers.insert("accept", "application/json".parse().unwrap());
@@ -38,18 +76,48 @@ impl HttpClient {
             None => HOP_API_BASE_URL.to_string(),
         };
 
+        let mut client = Self::client_builder(&ua, &headers, &options);
+
+        if let Some(timeout) = options.timeout {
+            client = client.timeout(timeout);
+        }
+
         Self {
-            client: AsyncClient::builder()
-                .user_agent(ua.clone())
-                .default_headers(headers.clone())
-                .build()
-                .unwrap(),
+            client: client.build().unwrap(),
             base_url,
             headers,
             ua,
+            options,
         }
     }
 
+    fn client_builder(
+        ua: &str,
+        headers: &HeaderMap,
+        options: &HttpOptions,
+    ) -> reqwest::ClientBuilder {
+        let mut client = AsyncClient::builder()
+            .user_agent(ua)
+            .default_headers(headers.clone());
+
+        if let Some(timeout) = options.timeout {
+            client = client.connect_timeout(timeout);
+        }
+
+        // proxies are picked up from the environment by reqwest itself
+        for cert in network::reqwest_certificates() {
+            client = client.add_root_certificate(cert);
+        }
+
+        client
+    }
+
+    /// Client for uploads, the timeout only limits connecting since
+    /// sending a large body can take longer than any request should
+    pub fn upload_client(&self) -> Result<AsyncClient> {
+        Ok(Self::client_builder(&self.ua, &self.headers, &self.options).build()?)
+    }
+
     pub async fn handle_response<T>(&self, response: reqwest::Response) -> Result<Option<T>>
     where
         T: serde::de::DeserializeOwned,
@@ -81,15 +149,22 @@ impl HttpClient {
         let body = response.json::<ErrorResponse>().await;
 
         match body {
-            Ok(body) => bail!("{}", body.error.message),
+            Ok(body) => {
+                Err(HopError::api(status, Some(body.error.code), body.error.message).into())
+            }
             Err(err) => {
                 log::debug!("Error deserialize message: {:#?}", err);
 
-                bail!(
-                    "HTTP {}: {}",
-                    status.as_u16(),
-                    status.canonical_reason().unwrap_or("Unknown")
+                Err(HopError::api(
+                    status,
+                    None,
+                    format!(
+                        "HTTP {}: {}",
+                        status.as_u16(),
+                        status.canonical_reason().unwrap_or("Unknown")
+                    ),
                 )
+                .into())
             }
         }
     }
@@ -116,9 +191,25 @@ impl HttpClient {
     where
         T: serde::de::DeserializeOwned,
     {
+        let response = self.send_with_query(method, path, query, data).await?;
+
+        self.handle_response(response).await
+    }
+
+    /// Sends a request and retries transient failures,
+    /// the response is returned as is without checking its status
+    pub async fn send_with_query(
+        &self,
+        method: &str,
+        path: &str,
+        query: Option<&[&[&str; 2]]>,
+        data: Option<(reqwest::Body, &str)>,
+    ) -> Result<reqwest::Response> {
+        let method = method.parse::<Method>()?;
+
         let mut request = self
             .client
-            .request(method.parse()?, format!("{}{path}", self.base_url));
+            .request(method.clone(), format!("{}{path}", self.base_url));
 
         log::debug!("request: {} {} {:?}", method, path, data);
 
@@ -139,16 +230,273 @@ impl HttpClient {
             request = request.body(body);
         }
 
-        let request = request.build()?;
+        let mut request = request.build()?;
+        let mut attempt = 0;
 
-        #[cfg(debug_assertions)]
-        let now = tokio::time::Instant::now();
+        let response = loop {
+            // streamed bodies cannot be cloned, so those requests are only sent once
+            let retry = request
+                .try_clone()
+                .filter(|_| attempt < self.options.max_retries);
 
-        let response = self.client.execute(request).await?;
+            #[cfg(debug_assertions)]
+            let now = tokio::time::Instant::now();
 
-        #[cfg(debug_assertions)]
-        log::debug!("response in: {:#?}", now.elapsed());
+            let result = self.client.execute(request).await;
 
-        self.handle_response(response).await
+            #[cfg(debug_assertions)]
+            log::debug!("response in: {:#?}", now.elapsed());
+
+            let Some(retry) = retry else {
+                break result.map_err(network_error)?;
+            };
+
+            let delay = match &result {
+                Ok(response) => self.retry_delay(&method, response, attempt),
+                Err(error)
+                    if is_idempotent(&method) && (error.is_connect() || error.is_timeout()) =>
+                {
+                    Some(self.backoff(attempt))
+                }
+                Err(_) => None,
+            };
+
+            let Some(delay) = delay else {
+                break result.map_err(network_error)?;
+            };
+
+            attempt += 1;
+
+            log::debug!(
+                "Retrying {method} {path} in {delay:?} ({attempt}/{})",
+                self.options.max_retries
+            );
+
+            tokio::time::sleep(delay).await;
+
+            request = retry;
+        };
+
+        Ok(response)
+    }
+
+    /// Returns how long to wait before retrying, or `None` if the response should not be retried
+    fn retry_delay(
+        &self,
+        method: &Method,
+        response: &reqwest::Response,
+        attempt: u32,
+    ) -> Option<Duration> {
+        match response.status() {
+            // rate limited requests were not processed so they are safe to retry
+            StatusCode::TOO_MANY_REQUESTS => Some(
+                parse_retry_after(response.headers())
+                    .map(|delay| delay.min(MAX_RETRY_DELAY))
+                    .unwrap_or_else(|| self.backoff(attempt)),
+            ),
+
+            StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+                if is_idempotent(method) =>
+            {
+                Some(self.backoff(attempt))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Exponential backoff with jitter, half of the delay is randomized
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .options
+            .backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RETRY_DELAY);
+
+        delay / 2 + delay.mul_f64(rand::thread_rng().gen_range(0.0..0.5))
+    }
+}
+
+fn network_error(error: reqwest::Error) -> anyhow::Error {
+    if error.is_connect() || error.is_timeout() {
+        HopError::new(ErrorKind::Network, error).into()
+    } else {
+        error.into()
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+/// Parses the `Retry-After` header, which is either an amount of seconds or a HTTP date
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Response, Server};
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Starts a local API that replies with the given responses in order,
+    /// returns the base url and the amount of requests received
+    pub(in crate::state::http) fn stub_server(
+        responses: Vec<Response<Body>>,
+    ) -> (String, Arc<Mutex<usize>>) {
+        let responses = Arc::new(Mutex::new(responses.into_iter()));
+        let hits = Arc::new(Mutex::new(0));
+
+        let counter = hits.clone();
+
+        let service = make_service_fn(move |_| {
+            let responses = responses.clone();
+            let counter = counter.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |_| {
+                    *counter.lock().unwrap() += 1;
+
+                    let response = responses.lock().unwrap().next().unwrap();
+
+                    async move { Ok::<_, Infallible>(response) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(service);
+        let url = format!("http://{}", server.local_addr());
+
+        tokio::spawn(server);
+
+        (url, hits)
+    }
+
+    pub(in crate::state::http) fn response(status: u16, body: &str) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn client(url: String) -> HttpClient {
+        HttpClient::with_options(
+            None,
+            Some(url),
+            HttpOptions {
+                backoff: Duration::from_millis(1),
+                ..Default::default()
+            },
+        )
+    }
+
+    const OK: &str = r#"{"success":true,"data":{"ok":true}}"#;
+
+    #[tokio::test]
+    async fn test_retry_on_unavailable() {
+        let (url, hits) = stub_server(vec![
+            response(503, ""),
+            response(502, ""),
+            response(200, OK),
+        ]);
+
+        let data = client(url)
+            .request::<Value>("GET", "/test", None)
+            .await
+            .unwrap();
+
+        assert_eq!(data, Some(serde_json::json!({ "ok": true })));
+        assert_eq!(*hits.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_for_non_idempotent() {
+        let (url, hits) = stub_server(vec![response(503, ""), response(200, OK)]);
+
+        let result = client(url)
+            .request::<Value>("POST", "/test", Some(("{}".into(), "application/json")))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(*hits.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_rate_limit() {
+        let mut limited = response(429, "");
+        limited
+            .headers_mut()
+            .insert(RETRY_AFTER, "0".parse().unwrap());
+
+        let (url, hits) = stub_server(vec![limited, response(200, OK)]);
+
+        let data = client(url)
+            .request::<Value>("POST", "/test", Some(("{}".into(), "application/json")))
+            .await
+            .unwrap();
+
+        assert!(data.is_some());
+        assert_eq!(*hits.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, hits) = stub_server((0..4).map(|_| response(503, "")).collect());
+
+        let result = client(url).request::<Value>("GET", "/test", None).await;
+
+        assert!(result.is_err());
+        assert_eq!(*hits.lock().unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_typed_api_error() {
+        let (url, _) = stub_server(vec![response(
+            404,
+            r#"{"success":false,"error":{"code":"deployment_not_found","message":"Deployment not found"}}"#,
+        )]);
+
+        let error = client(url)
+            .request::<Value>("GET", "/test", None)
+            .await
+            .unwrap_err();
+
+        let error = error.downcast_ref::<HopError>().unwrap();
+
+        assert_eq!(error.kind, ErrorKind::NotFound);
+        assert_eq!(error.status, Some(404));
+        assert_eq!(error.code.as_deref(), Some("deployment_not_found"));
+        assert_eq!(error.to_string(), "Deployment not found");
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+
+        assert_eq!(parse_retry_after(&headers), None);
     }
 }
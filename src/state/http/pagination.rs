@@ -0,0 +1,348 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::HttpClient;
+
+pub const DEFAULT_PAGE_SIZE: u64 = 75;
+
+/// A single page of a paginated collection
+pub trait Paginated: DeserializeOwned {
+    type Item;
+
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// Total amount of items in the collection, if the API reports it
+    fn total_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Cursor of the next page, if the API uses cursor based pagination
+    fn next_cursor(&self) -> Option<String> {
+        None
+    }
+}
+
+#[macro_export]
+macro_rules! impl_paginated {
+    ($($name:ty => $field:ident: $item:ty),+ $(,)?) => ($(
+        impl $crate::state::http::pagination::Paginated for $name {
+            type Item = $item;
+
+            fn into_items(self) -> Vec<Self::Item> {
+                self.$field
+            }
+        }
+    )+)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageStyle {
+    /// `page` and `pageSize` query parameters
+    #[default]
+    Page,
+    /// The API returns the whole collection at once, `limit` is applied locally
+    Unpaginated,
+}
+
+#[derive(Debug, Parser, Clone, Copy, PartialEq, Eq)]
+#[group(skip)]
+pub struct PageOptions {
+    #[clap(long, help = "Maximum amount of items to fetch")]
+    pub limit: Option<u64>,
+
+    #[clap(
+        long,
+        help = "Amount of items to fetch per request",
+        default_value_t = DEFAULT_PAGE_SIZE,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub page_size: u64,
+}
+
+/// Only `--limit`, for commands whose endpoint returns the whole collection at once
+#[derive(Debug, Parser, Clone, Copy, Default, PartialEq, Eq)]
+#[group(skip)]
+pub struct LimitOptions {
+    #[clap(long, help = "Maximum amount of items to fetch")]
+    pub limit: Option<u64>,
+}
+
+impl From<LimitOptions> for PageOptions {
+    fn from(options: LimitOptions) -> Self {
+        Self {
+            limit: options.limit,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+struct Cursor {
+    page: u64,
+    fetched: u64,
+    cursor: Option<String>,
+    done: bool,
+    /// previous page, an endpoint that ignores the paging returns the same page again
+    previous: Option<Value>,
+}
+
+impl HttpClient {
+    /// Fetches every item of a paginated collection,
+    /// stops early once `limit` items are fetched
+    pub async fn request_all<P>(
+        &self,
+        path: &str,
+        style: PageStyle,
+        options: PageOptions,
+    ) -> Result<Vec<P::Item>>
+    where
+        P: Paginated,
+    {
+        self.request_stream::<P>(path, style, options)
+            .try_collect()
+            .await
+    }
+
+    /// Lazily fetches the pages of a paginated collection,
+    /// the next page is only requested once the current one is consumed
+    pub fn request_stream<'a, P>(
+        &'a self,
+        path: &'a str,
+        style: PageStyle,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<P::Item>> + 'a
+    where
+        P: Paginated + 'a,
+    {
+        let start = Cursor {
+            page: 1,
+            fetched: 0,
+            cursor: None,
+            done: false,
+            previous: None,
+        };
+
+        stream::try_unfold(start, move |mut state| async move {
+            let remaining = options.limit.map(|limit| limit - state.fetched);
+
+            if state.done || remaining == Some(0) {
+                return Ok::<_, anyhow::Error>(None);
+            }
+
+            let page_size = remaining.map_or(options.page_size, |r| r.min(options.page_size));
+            let position = match style {
+                PageStyle::Page => Some(("page", state.page, "pageSize")),
+                PageStyle::Unpaginated => None,
+            };
+
+            let mut query = vec![];
+
+            if let Some((position_key, position, size_key)) = position {
+                query.push((position_key, position.to_string()));
+                query.push((size_key, page_size.to_string()));
+            }
+
+            let used_cursor = state.cursor.is_some();
+
+            if let Some(cursor) = &state.cursor {
+                query.push(("cursor", cursor.clone()));
+            }
+
+            let query = query
+                .iter()
+                .map(|(key, value)| [*key, value.as_str()])
+                .collect::<Vec<_>>();
+            let query = query.iter().collect::<Vec<_>>();
+
+            let data = self
+                .request_with_query::<Value>("GET", path, Some(&query), None)
+                .await?
+                .ok_or_else(|| anyhow!("Error while parsing response"))?;
+
+            // the same page again means the endpoint ignores the paging
+            if state.previous.as_ref() == Some(&data) {
+                return Ok(None);
+            }
+
+            let response = serde_json::from_value::<P>(data.clone())
+                .map_err(|error| anyhow!("Error while parsing response: {error}"))?;
+
+            let total_count = response.total_count();
+            state.cursor = response.next_cursor();
+
+            let mut items = response.into_items();
+
+            // endpoints without pagination return the whole collection at once
+            let unpaginated = position.is_none() || items.len() > page_size as usize;
+
+            if let Some(remaining) = remaining {
+                items.truncate(remaining as usize);
+            }
+
+            state.page += 1;
+            state.fetched += items.len() as u64;
+            state.previous = Some(data);
+
+            // a short page means this was the last one, cursor based apis tell us explicitly
+            state.done = unpaginated
+                || items.is_empty()
+                || items.len() < page_size as usize
+                || total_count.is_some_and(|total| state.fetched >= total)
+                || (used_cursor && state.cursor.is_none());
+
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), state)))
+        })
+        .try_flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::state::http::test::{response, stub_server};
+
+    #[derive(Debug, Deserialize)]
+    struct Numbers {
+        numbers: Vec<u64>,
+    }
+
+    impl_paginated!(Numbers => numbers: u64);
+
+    #[tokio::test]
+    async fn test_request_all_pages() {
+        let (url, hits) = stub_server(vec![
+            response(200, r#"{"success":true,"data":{"numbers":[1,2]}}"#),
+            response(200, r#"{"success":true,"data":{"numbers":[3,4]}}"#),
+            response(200, r#"{"success":true,"data":{"numbers":[5]}}"#),
+        ]);
+
+        let numbers = HttpClient::new(None, Some(url))
+            .request_all::<Numbers>(
+                "/numbers",
+                PageStyle::Page,
+                PageOptions {
+                    limit: None,
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+        assert_eq!(*hits.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_limit() {
+        let (url, hits) = stub_server(vec![
+            response(200, r#"{"success":true,"data":{"numbers":[1,2]}}"#),
+            response(200, r#"{"success":true,"data":{"numbers":[3]}}"#),
+        ]);
+
+        let numbers = HttpClient::new(None, Some(url))
+            .request_all::<Numbers>(
+                "/numbers",
+                PageStyle::Page,
+                PageOptions {
+                    limit: Some(3),
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(*hits.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_unpaginated() {
+        let (url, hits) = stub_server(vec![response(
+            200,
+            r#"{"success":true,"data":{"numbers":[1,2,3]}}"#,
+        )]);
+
+        let numbers = HttpClient::new(None, Some(url))
+            .request_all::<Numbers>(
+                "/numbers",
+                PageStyle::Page,
+                PageOptions {
+                    limit: None,
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(*hits.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_ignored_paging() {
+        // exactly a page of items, returned again for the next page
+        let (url, hits) = stub_server(vec![
+            response(200, r#"{"success":true,"data":{"numbers":[1,2]}}"#),
+            response(200, r#"{"success":true,"data":{"numbers":[1,2]}}"#),
+        ]);
+
+        let numbers = HttpClient::new(None, Some(url))
+            .request_all::<Numbers>(
+                "/numbers",
+                PageStyle::Page,
+                PageOptions {
+                    limit: None,
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(*hits.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_single_request() {
+        let (url, hits) = stub_server(vec![response(
+            200,
+            r#"{"success":true,"data":{"numbers":[1,2]}}"#,
+        )]);
+
+        let numbers = HttpClient::new(None, Some(url))
+            .request_all::<Numbers>(
+                "/numbers",
+                PageStyle::Unpaginated,
+                PageOptions {
+                    limit: None,
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(*hits.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_into_items() {
+        let page: Numbers = serde_json::from_str(r#"{"numbers":[1,2,3]}"#).unwrap();
+
+        assert_eq!(page.total_count(), None);
+        assert_eq!(page.into_items(), vec![1, 2, 3]);
+    }
+}
@@ -1,15 +1,18 @@
 pub mod http;
+mod resolve;
+
 use anyhow::{ensure, Context as AnyhyowContext, Result};
-use hop::{Hop, HopOptions};
 
-use self::http::HttpClient;
+use self::http::{HttpClient, HttpOptions};
 use crate::commands::auth::login::util::{token_options, TokenType};
-use crate::commands::ignite::types::Deployment;
-use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
 use crate::config::EXEC_NAME;
+use crate::errors::{ErrorKind, HopError};
 use crate::store::auth::Auth;
+use crate::store::config::Config;
 use crate::store::context::Context;
 use crate::store::Store;
+use crate::utils::logging;
+use crate::utils::output::OutputFormat;
 
 #[derive(Debug)]
 pub struct State {
@@ -18,16 +21,24 @@ pub struct State {
     pub ctx: Context,
     pub http: HttpClient,
     pub debug: bool,
-    pub hop: Hop,
+    pub output: OutputFormat,
+    pub config: Config,
+    /// Whether the project was passed with `--project` or `PROJECT_ID`
+    /// instead of being the default of the context
+    pub explicit_project: bool,
     token: Option<String>,
     token_type: Option<TokenType>,
 }
 
 #[derive(Debug, Default)]
 pub struct StateOptions {
+    pub override_context: Option<String>,
     pub override_project: Option<String>,
     pub override_token: Option<String>,
     pub debug: bool,
+    pub output: OutputFormat,
+    pub http: HttpOptions,
+    pub config: Config,
 }
 
 impl State {
@@ -36,6 +47,13 @@ impl State {
         let auth = Auth::new().await?;
         let mut ctx = Context::new().await?;
 
+        // select the context before anything reads from it
+        if let Some(name) = options.override_context {
+            ctx.select_context(&name)?;
+        }
+
+        let explicit_project = options.override_project.is_some();
+
         // override the project id if provided
         ctx.project_override = options
             .override_project
@@ -44,10 +62,10 @@ impl State {
         // use the override token if provided
         let init_token = if let Some(override_token) = options.override_token {
             Some(override_token)
-        // otherwise use the token from the store
+        // otherwise use the token from the store, if it is open
         } else if let Some(ref user) = ctx.default_user {
             auth.authorized.get(user).map(|x| x.to_string())
-        // if all fail then no token
+        // if all fail then no token, until the credential store is unlocked
         } else {
             None
         };
@@ -59,22 +77,19 @@ impl State {
             .or_else(|| ctx.override_api_url.clone());
 
         // preffer the override token over the auth token
-        let http = HttpClient::new(token.clone(), api_url.clone());
-
-        let hop = Hop::new_with_options(HopOptions {
-            token: token.clone(),
-            api_url,
-        })?;
+        let http = HttpClient::with_options(token.clone(), api_url, options.http);
 
         Ok(State {
             is_ci: Self::check_if_ci(),
             token_type,
             token,
             http,
-            hop,
             auth,
             ctx,
             debug: options.debug,
+            output: options.output,
+            config: options.config,
+            explicit_project,
         })
     }
 
@@ -88,7 +103,7 @@ impl State {
     }
 
     /// Checks if the current environment is a CI environment.
-    fn check_if_ci() -> bool {
+    pub fn check_if_ci() -> bool {
         std::env::vars().any(|(key, _)| {
             matches!(
                 key.as_str(),
@@ -105,10 +120,16 @@ impl State {
 
     /// Login to the API
     pub async fn login(&mut self, token: Option<String>) -> Result<()> {
+        if token.is_none() {
+            self.unlock_auth().await?;
+        }
+
         ensure!(
             token.is_some() || self.token.is_some(),
-            "You are not logged in. Please run `{} auth login` first.",
-            EXEC_NAME
+            HopError::new(
+                ErrorKind::Auth,
+                format!("You are not logged in. Please run `{EXEC_NAME} auth login` first.")
+            )
         );
 
         if let Some(token) = token {
@@ -116,7 +137,11 @@ impl State {
 
             self.token = token.clone();
             self.token_type = token_type;
-            self.http = HttpClient::new(token, self.ctx.override_api_url.clone());
+            self.http = HttpClient::with_options(
+                token,
+                self.ctx.override_api_url.clone(),
+                self.http.options.clone(),
+            );
         }
 
         let response = token_options(self.http.clone(), self.token_type.clone()).await?;
@@ -133,50 +158,47 @@ impl State {
             self.ctx.project_override = self.ctx.current.as_ref().map(|cur| cur.id.clone())
         }
 
-        Ok(())
-    }
+        if let Some(project) = self.ctx.current_project() {
+            logging::set_context("project", project.id);
+        }
 
-    pub fn token(&self) -> Option<String> {
-        self.token.clone()
+        Ok(())
     }
 
-    pub async fn get_deployment_by_name_or_id(&self, name_or_id: &str) -> Result<Deployment> {
-        // deployments cannot contain underscores so we can use this to determine if
-        // it's an id
-        if name_or_id.starts_with("deployment_") {
-            return get_deployment(&self.http, name_or_id).await;
+    /// Opens the credential store, and uses the token of the current user
+    /// if no token was passed. Only done when a token is needed since the
+    /// encrypted store asks for its passphrase
+    pub async fn unlock_auth(&mut self) -> Result<()> {
+        // the token from the environment does not need the store
+        if self.token.is_some() {
+            return Ok(());
         }
 
-        let deployments =
-            get_all_deployments(&self.http, &self.ctx.current_project_error()?.id).await?;
-
-        let deployment = deployments
-            .into_iter()
-            .find(|d| d.name == name_or_id)
-            .context("Deployment not found")?;
+        self.auth.unlock().await?;
 
-        Ok(deployment)
-    }
+        let Some(token) = self
+            .ctx
+            .default_user
+            .as_ref()
+            .and_then(|user| self.auth.authorized.get(user))
+            .cloned()
+        else {
+            return Ok(());
+        };
 
-    pub async fn get_deployment_by_opt_name_or_id(
-        &self,
-        name_or_id: Option<&str>,
-    ) -> Result<Deployment> {
-        if let Some(name_or_id) = name_or_id {
-            self.get_deployment_by_name_or_id(name_or_id).await
-        } else {
-            let deployments =
-                get_all_deployments(&self.http, &self.ctx.current_project_error()?.id).await?;
+        let (token, token_type) = Self::handle_token(Some(token))?;
+        let api_url = std::env::var("API_URL")
+            .ok()
+            .or_else(|| self.ctx.override_api_url.clone());
 
-            let deployments_fmt = format_deployments(&deployments, false);
+        self.http = HttpClient::with_options(token.clone(), api_url, self.http.options.clone());
+        self.token = token;
+        self.token_type = token_type;
 
-            let idx = dialoguer::Select::new()
-                .with_prompt("Select a deployment")
-                .items(&deployments_fmt)
-                .default(0)
-                .interact()?;
+        Ok(())
+    }
 
-            Ok(deployments[idx].clone())
-        }
+    pub fn token(&self) -> Option<String> {
+        self.token.clone()
     }
 }
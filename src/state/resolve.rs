@@ -0,0 +1,277 @@
+//! Lookup of resources by the references users pass on the command line,
+//! see [`crate::utils::resolve`] for how a reference is matched.
+//! Resources that belong to a deployment can also be referenced as
+//! `<deployment>/<index>` or `<deployment>/<id or name>`, e.g. `api/0`
+
+use anyhow::{bail, ensure, Context, Result};
+
+use super::State;
+use crate::commands::channels::tokens::types::LeapToken;
+use crate::commands::channels::tokens::utils::get_all_tokens;
+use crate::commands::channels::types::Channel;
+use crate::commands::channels::utils::get_all_channels;
+use crate::commands::containers::types::Container;
+use crate::commands::containers::utils::{get_all_containers, get_container};
+use crate::commands::domains::types::Domain;
+use crate::commands::domains::util::get_all_domains;
+use crate::commands::gateways::types::Gateway;
+use crate::commands::gateways::util::{get_all_gateways, get_gateway};
+use crate::commands::ignite::builds::types::Build;
+use crate::commands::ignite::builds::utils::get_all_builds;
+use crate::commands::ignite::health::types::HealthCheck;
+use crate::commands::ignite::health::utils::get_all_health_checks;
+use crate::commands::ignite::types::Deployment;
+use crate::commands::ignite::utils::{format_deployments, get_all_deployments, get_deployment};
+use crate::commands::projects::types::Project;
+use crate::config::EXEC_NAME;
+use crate::errors::{ErrorKind, HopError};
+use crate::store::hopfile::HopFile;
+use crate::utils::resolve::{find_by_index_or_ref, find_one, split_reference};
+use crate::utils::{logging, prompt};
+
+impl State {
+    /// Deployments of the current project
+    async fn deployments(&self) -> Result<Vec<Deployment>> {
+        get_all_deployments(&self.http, &self.ctx.current_project_error()?.id).await
+    }
+
+    /// Finds a deployment by its ID, name or a prefix of its ID
+    pub async fn get_deployment_by_name_or_id(&self, name_or_id: &str) -> Result<Deployment> {
+        // deployment names cannot contain underscores so this is an ID or a prefix of
+        // one, deployments of other projects can only be found by their full ID
+        let direct = if name_or_id.starts_with("deployment_") {
+            not_found_as_none(get_deployment(&self.http, name_or_id).await)?
+        } else {
+            None
+        };
+
+        let deployment = match direct {
+            Some(deployment) => deployment,
+            None => find_one(&self.deployments().await?, name_or_id)?.clone(),
+        };
+
+        logging::set_context("deployment", &deployment.id);
+
+        Ok(deployment)
+    }
+
+    pub async fn get_deployment_by_opt_name_or_id(
+        &self,
+        name_or_id: Option<&str>,
+    ) -> Result<Deployment> {
+        if let Some(name_or_id) = name_or_id {
+            self.get_deployment_by_name_or_id(name_or_id).await
+        } else {
+            let deployments = self.deployments().await?;
+            ensure!(!deployments.is_empty(), "No deployments found");
+
+            let deployments_fmt = format_deployments(&deployments, false);
+
+            let idx = prompt::Select::new("<DEPLOYMENT>")
+                .with_prompt("Select a deployment")
+                .items(&deployments_fmt)
+                .default(0)
+                .interact()?;
+
+            logging::set_context("deployment", &deployments[idx].id);
+
+            Ok(deployments[idx].clone())
+        }
+    }
+
+    /// Project of a hopfile, the current project if the hopfile does not name one
+    pub fn get_hopfile_project(&self, hopfile: &HopFile) -> Result<Project> {
+        if hopfile.config.project_id.is_empty() {
+            return self.ctx.current_project_error();
+        }
+
+        self.ctx
+            .find_project_by_id_or_namespace(&hopfile.config.project_id)
+            .with_context(|| {
+                format!(
+                    "Could not find project with id {}",
+                    hopfile.config.project_id
+                )
+            })
+    }
+
+    /// Deployment of a hopfile, `None` if it only describes one that was not created yet
+    pub async fn find_hopfile_deployment(&self, hopfile: &HopFile) -> Result<Option<Deployment>> {
+        if !hopfile.config.deployment_id.is_empty() {
+            return get_deployment(&self.http, &hopfile.config.deployment_id)
+                .await
+                .map(Some);
+        }
+
+        let Some(name) = hopfile.deployment_ref() else {
+            return Ok(None);
+        };
+
+        let project = self.get_hopfile_project(hopfile)?;
+
+        Ok(get_all_deployments(&self.http, &project.id)
+            .await?
+            .into_iter()
+            .find(|deployment| deployment.name == name))
+    }
+
+    /// Like [`Self::find_hopfile_deployment`] but fails if it does not exist
+    pub async fn get_hopfile_deployment(&self, hopfile: &HopFile) -> Result<Deployment> {
+        if hopfile.deployment_ref().is_none() && !hopfile.services.is_empty() {
+            bail!(
+                "{} lists the services of a monorepo, run the command in the directory of one of them",
+                hopfile.path.display()
+            );
+        }
+
+        self.find_hopfile_deployment(hopfile)
+            .await?
+            .with_context(|| {
+                format!(
+                    "The deployment in {} does not exist yet, run `{EXEC_NAME} apply` to create it",
+                    hopfile.path.display()
+                )
+            })
+    }
+
+    /// Finds a container by its ID, a prefix of its ID or `<deployment>/<index>`
+    pub async fn get_container_by_name_or_id(&self, reference: &str) -> Result<Container> {
+        if let Some((deployment, container)) = split_reference(reference) {
+            let deployment = self.get_deployment_by_name_or_id(deployment).await?;
+            let containers = get_all_containers(&self.http, &deployment.id).await?;
+
+            return Ok(find_by_index_or_ref(&containers, container)?.clone());
+        }
+
+        if reference.starts_with("container_") {
+            if let Some(container) = not_found_as_none(get_container(&self.http, reference).await)?
+            {
+                return Ok(container);
+            }
+        }
+
+        let mut containers = vec![];
+
+        for deployment in self.deployments().await? {
+            containers.extend(get_all_containers(&self.http, &deployment.id).await?);
+        }
+
+        Ok(find_one(&containers, reference)?.clone())
+    }
+
+    /// Finds a Gateway by its ID, name, a prefix of its ID or `<deployment>/<index>`
+    pub async fn get_gateway_by_name_or_id(&self, reference: &str) -> Result<Gateway> {
+        if let Some((deployment, gateway)) = split_reference(reference) {
+            let deployment = self.get_deployment_by_name_or_id(deployment).await?;
+            let gateways = get_all_gateways(&self.http, &deployment.id).await?;
+
+            return Ok(find_by_index_or_ref(&gateways, gateway)?.clone());
+        }
+
+        if reference.starts_with("gateway_") {
+            if let Some(gateway) = not_found_as_none(get_gateway(&self.http, reference).await)? {
+                return Ok(gateway);
+            }
+        }
+
+        let mut gateways = vec![];
+
+        for deployment in self.deployments().await? {
+            gateways.extend(get_all_gateways(&self.http, &deployment.id).await?);
+        }
+
+        Ok(find_one(&gateways, reference)?.clone())
+    }
+
+    /// Finds a domain by its ID, the domain itself or `<gateway>/<index>`
+    pub async fn get_domain_by_name_or_id(&self, reference: &str) -> Result<Domain> {
+        // domains contain dots but never slashes
+        if let Some((gateway, domain)) = split_reference(reference) {
+            let gateway = self.get_gateway_by_name_or_id(gateway).await?;
+            let domains = get_all_domains(&self.http, &gateway.id).await?;
+
+            return Ok(find_by_index_or_ref(&domains, domain)?.clone());
+        }
+
+        let mut domains = vec![];
+
+        for deployment in self.deployments().await? {
+            for gateway in get_all_gateways(&self.http, &deployment.id).await? {
+                domains.extend(get_all_domains(&self.http, &gateway.id).await?);
+            }
+        }
+
+        Ok(find_one(&domains, reference)?.clone())
+    }
+
+    /// Finds a channel of the current project by its ID or a prefix of it
+    pub async fn get_channel_by_name_or_id(&self, reference: &str) -> Result<Channel> {
+        let channels = get_all_channels(&self.http, &self.ctx.current_project_error()?.id).await?;
+
+        Ok(find_one(&channels, reference)?.clone())
+    }
+
+    /// Finds a Leap Token of the current project by its ID or a prefix of it
+    pub async fn get_token_by_name_or_id(&self, reference: &str) -> Result<LeapToken> {
+        let tokens = get_all_tokens(&self.http, &self.ctx.current_project_error()?.id).await?;
+
+        Ok(find_one(&tokens, reference)?.clone())
+    }
+
+    /// Finds a health check by its ID, a prefix of its ID or `<deployment>/<index>`
+    pub async fn get_health_check_by_name_or_id(&self, reference: &str) -> Result<HealthCheck> {
+        if let Some((deployment, health_check)) = split_reference(reference) {
+            let deployment = self.get_deployment_by_name_or_id(deployment).await?;
+            let health_checks = get_all_health_checks(&self.http, &deployment.id).await?;
+
+            return Ok(find_by_index_or_ref(&health_checks, health_check)?.clone());
+        }
+
+        let mut health_checks = vec![];
+
+        for deployment in self.deployments().await? {
+            health_checks.extend(get_all_health_checks(&self.http, &deployment.id).await?);
+        }
+
+        Ok(find_one(&health_checks, reference)?.clone())
+    }
+
+    /// Finds a build by its ID, a prefix of its ID or `<deployment>/<index>`,
+    /// builds are sorted from newest to oldest so `api/0` is the latest build
+    pub async fn get_build_by_name_or_id(&self, reference: &str) -> Result<Build> {
+        if let Some((deployment, build)) = split_reference(reference) {
+            let deployment = self.get_deployment_by_name_or_id(deployment).await?;
+            let builds = get_all_builds(&self.http, &deployment.id).await?;
+
+            return Ok(find_by_index_or_ref(&builds, build)?.clone());
+        }
+
+        let mut builds = vec![];
+
+        for deployment in self.deployments().await? {
+            builds.extend(get_all_builds(&self.http, &deployment.id).await?);
+        }
+
+        Ok(find_one(&builds, reference)?.clone())
+    }
+
+    /// Finds a build of a deployment by its ID, a prefix of its ID or its index
+    pub async fn get_deployment_build_by_name_or_id(
+        &self,
+        deployment_id: &str,
+        reference: &str,
+    ) -> Result<Build> {
+        let builds = get_all_builds(&self.http, deployment_id).await?;
+
+        Ok(find_by_index_or_ref(&builds, reference)?.clone())
+    }
+}
+
+/// Turns a not found error into `None` so the lookup can fall back to a search
+fn not_found_as_none<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if HopError::kind_of(&error) == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
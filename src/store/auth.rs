@@ -1,24 +1,262 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use tokio::fs::{self, File};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::fs;
 
+use super::credentials::{Backend, CredentialStore, MemoryStore};
 use super::utils::home_path;
 use super::Storable;
-use crate::impl_store;
+use crate::config::EXEC_NAME;
 
+/// On disk format of the auth store, tokens are only
+/// kept in this file when the plaintext backend is used
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct AuthFile {
+    #[serde(default)]
+    backend: Backend,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    users: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    authorized: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Auth {
+    /// user id to token of every authorized user, empty until [`Auth::unlock`]
+    /// for backends other than the plaintext one
     pub authorized: HashMap<String, String>,
+    pub backend: Backend,
+    /// opened on first use, opening the encrypted file asks for its passphrase
+    store: Option<Arc<dyn CredentialStore>>,
+    /// users that currently have a token in the store
+    stored: Vec<String>,
 }
 
-impl Storable for Auth {
+impl Storable for AuthFile {
     fn path() -> Result<PathBuf> {
         home_path(".hop/auth.json")
     }
 }
 
-impl_store!(Auth);
+impl Auth {
+    pub async fn new() -> Result<Self> {
+        let path = AuthFile::path()?;
+
+        let file = if fs::metadata(&path).await.is_ok() {
+            let buffer = fs::read_to_string(&path)
+                .await
+                .context("Error opening file")?;
+
+            serde_json::from_str::<AuthFile>(&buffer).context("Failed to deserialize Auth store")?
+        } else {
+            AuthFile {
+                backend: Backend::preferred().await,
+                ..Default::default()
+            }
+        };
+
+        match file.backend {
+            Backend::Plaintext => {
+                Self::load(
+                    file.backend,
+                    Arc::new(MemoryStore::new(file.authorized.clone())),
+                    file.authorized.into_keys().collect(),
+                )
+                .await
+            }
+
+            backend => Ok(Self {
+                authorized: HashMap::new(),
+                backend,
+                store: None,
+                stored: file.users,
+            }),
+        }
+    }
+
+    /// Loads the tokens of the given users from a credential store
+    pub async fn load(
+        backend: Backend,
+        store: Arc<dyn CredentialStore>,
+        users: Vec<String>,
+    ) -> Result<Self> {
+        let mut auth = Self {
+            authorized: HashMap::new(),
+            backend,
+            store: None,
+            stored: users,
+        };
+
+        auth.load_tokens(store).await?;
+
+        Ok(auth)
+    }
+
+    /// Opens the credential store and loads the tokens from it, does nothing if it is open
+    pub async fn unlock(&mut self) -> Result<()> {
+        if self.store.is_none() {
+            let store = self.backend.open().await?.into();
+
+            self.load_tokens(store).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn load_tokens(&mut self, store: Arc<dyn CredentialStore>) -> Result<()> {
+        for user in &self.stored {
+            // tokens added before unlocking are newer
+            if self.authorized.contains_key(user) {
+                continue;
+            }
+
+            match store.get(user).await? {
+                Some(token) => {
+                    self.authorized.insert(user.clone(), token);
+                }
+
+                None => log::warn!(
+                    "No token found for user `{user}`, run `{EXEC_NAME} auth login` to login again"
+                ),
+            }
+        }
+
+        self.store = Some(store);
+
+        Ok(())
+    }
+
+    pub async fn save(&mut self) -> Result<()> {
+        self.sync().await?;
+
+        let mut file = AuthFile {
+            backend: self.backend,
+            ..Default::default()
+        };
+
+        if self.backend == Backend::Plaintext {
+            file.authorized = self.authorized.clone();
+        } else {
+            file.users = self.stored.clone();
+        }
+
+        let path = AuthFile::path()?;
+
+        fs::create_dir_all(path.parent().context("Failed to get store directory")?)
+            .await
+            .context("Failed to create store directory")?;
+
+        fs::write(
+            path,
+            serde_json::to_string(&file).context("Failed to serialize Auth store")?,
+        )
+        .await
+        .context("Failed to write store")
+    }
+
+    /// Writes every token to the credential store and removes the ones of logged out users
+    pub async fn sync(&mut self) -> Result<()> {
+        self.unlock().await?;
+
+        let store = self.store.clone().context("Credential store is not open")?;
+
+        for (user, token) in &self.authorized {
+            store.set(user, token).await?;
+        }
+
+        for user in &self.stored {
+            if !self.authorized.contains_key(user) {
+                store.delete(user).await?;
+            }
+        }
+
+        self.stored = self.authorized.keys().cloned().collect();
+        self.stored.sort();
+
+        Ok(())
+    }
+
+    /// Moves every token to another credential store
+    pub async fn migrate(
+        &mut self,
+        backend: Backend,
+        store: Arc<dyn CredentialStore>,
+    ) -> Result<()> {
+        self.unlock().await?;
+
+        let old = self.store.clone().context("Credential store is not open")?;
+
+        for (user, token) in &self.authorized {
+            store.set(user, token).await?;
+        }
+
+        for user in &self.stored {
+            old.delete(user).await?;
+        }
+
+        self.backend = backend;
+        self.store = Some(store);
+        self.stored = self.authorized.keys().cloned().collect();
+        self.stored.sort();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(user, token)| (user.to_string(), token.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_sync_removes_logged_out_users() {
+        let store = Arc::new(MemoryStore::new(tokens(&[
+            ("user_1", "pat_1"),
+            ("user_2", "pat_2"),
+        ])));
+
+        let mut auth = Auth::load(
+            Backend::Plaintext,
+            store.clone(),
+            vec!["user_1".to_string(), "user_2".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(auth.authorized.len(), 2);
+
+        auth.authorized.remove("user_1");
+        auth.authorized
+            .insert("user_3".to_string(), "pat_3".to_string());
+        auth.sync().await.unwrap();
+
+        assert_eq!(store.get("user_1").await.unwrap(), None);
+        assert_eq!(store.get("user_3").await.unwrap().as_deref(), Some("pat_3"));
+        assert_eq!(auth.stored, vec!["user_2", "user_3"]);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_moves_tokens() {
+        let old = Arc::new(MemoryStore::new(tokens(&[("user_1", "pat_1")])));
+        let new = Arc::new(MemoryStore::default());
+
+        let mut auth = Auth::load(Backend::Plaintext, old.clone(), vec!["user_1".to_string()])
+            .await
+            .unwrap();
+
+        auth.migrate(Backend::Keyring, new.clone()).await.unwrap();
+
+        assert_eq!(old.get("user_1").await.unwrap(), None);
+        assert_eq!(new.get("user_1").await.unwrap().as_deref(), Some("pat_1"));
+        assert_eq!(auth.backend, Backend::Keyring);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs::{self, File};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use super::utils::home_path;
+use super::Storable;
+use crate::impl_store;
+
+/// Resource names used by shell completions, keyed by resource and project
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CompletionCache {
+    #[serde(default)]
+    pub entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheEntry {
+    pub updated_at: DateTime<Utc>,
+    pub values: Vec<String>,
+}
+
+impl Storable for CompletionCache {
+    fn path() -> Result<PathBuf> {
+        home_path(".hop/cache/completions.json")
+    }
+}
+
+impl_store!(CompletionCache);
+
+impl CompletionCache {
+    /// Values that were fetched less than `max_age` ago
+    pub fn get_fresh(&self, key: &str, max_age: Duration) -> Option<&[String]> {
+        self.entries
+            .get(key)
+            .filter(|entry| Utc::now() - entry.updated_at < max_age)
+            .map(|entry| entry.values.as_slice())
+    }
+
+    /// Values of any age, used when the API cannot be reached
+    pub fn get(&self, key: &str) -> Option<&[String]> {
+        self.entries.get(key).map(|entry| entry.values.as_slice())
+    }
+
+    pub fn insert(&mut self, key: String, values: Vec<String>) {
+        self.entries.insert(
+            key,
+            CacheEntry {
+                updated_at: Utc::now(),
+                values,
+            },
+        );
+    }
+}
+
+/// Hash of the files each deployment was last released from, to skip unchanged deploys
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BuildCache {
+    #[serde(default)]
+    pub deployments: BTreeMap<String, BuildRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildRecord {
+    pub hash: String,
+    pub build_id: String,
+    pub built_at: DateTime<Utc>,
+}
+
+impl Storable for BuildCache {
+    fn path() -> Result<PathBuf> {
+        home_path(".hop/cache/builds.json")
+    }
+}
+
+impl_store!(BuildCache);
+
+impl BuildCache {
+    pub fn get(&self, deployment_id: &str) -> Option<&BuildRecord> {
+        self.deployments.get(deployment_id)
+    }
+
+    pub fn insert(&mut self, deployment_id: String, build_id: String, hash: String) {
+        self.deployments.insert(
+            deployment_id,
+            BuildRecord {
+                hash,
+                build_id,
+                built_at: Utc::now(),
+            },
+        );
+    }
+}
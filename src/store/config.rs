@@ -0,0 +1,418 @@
+use std::env::current_dir;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use toml::{Table, Value};
+
+use super::hopfile::HopFile;
+use super::utils::home_path;
+use crate::config::{DEFAULT_EDITOR, DEFAULT_MAX_UPLOAD_SIZE, EXEC_NAME};
+use crate::utils::logging::LogFormat;
+use crate::utils::output::OutputFormat;
+use crate::utils::size::parse_size;
+
+/// Path of the config file, relative to the home directory
+/// for the user config and to the Hopfile for the project config
+const CONFIG_PATH: &str = ".hop/config.toml";
+
+/// Keys a cloned repository could abuse, they are only read
+/// from the project config with `trust_project_config` set
+const TRUSTED_KEYS: &[&str] = &["ca_cert", "editor"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DeployMode {
+    /// Build on the Hop builder
+    #[default]
+    Remote,
+    /// Build locally with nixpacks or docker
+    Local,
+}
+
+impl FromStr for DeployMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_str(&format!("\"{}\"", s.to_lowercase())).map_err(|e| anyhow!(e))
+    }
+}
+
+impl Display for DeployMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).unwrap().replace('"', "")
+        )
+    }
+}
+
+/// User configuration, every value is optional and falls back to the built-in default
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// default of `--output`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+    /// editor used to edit files, overrides `EDITOR` and `VISUAL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// never prompt, like always passing `--no-input`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_input: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorMode>,
+    /// extra trusted certificates in PEM format, default of `--ca-cert`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    /// default of `--log-format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+    /// let project configs set `ca_cert` and `editor`, only read from the user config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust_project_config: Option<bool>,
+    pub deploy: DeployConfig,
+    pub logs: LogsConfig,
+    pub health_check: HealthCheckConfig,
+    /// keys of the project config that were ignored because it is not trusted
+    #[serde(skip)]
+    pub ignored_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeployConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<DeployMode>,
+    /// tier used when no resources are passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ram: Option<String>,
+    /// largest archive uploaded to the builder, like `500MB`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_upload_size: Option<String>,
+}
+
+impl DeployConfig {
+    pub fn max_upload_size(&self) -> Result<u64> {
+        self.max_upload_size
+            .as_deref()
+            .map_or(Ok(DEFAULT_MAX_UPLOAD_SIZE), parse_size)
+            .context("Invalid `deploy.max_upload_size` in the config")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthCheckConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_delay: Option<u64>,
+}
+
+impl Config {
+    pub fn user_path() -> Result<PathBuf> {
+        home_path(CONFIG_PATH)
+    }
+
+    /// Config of the project the current directory belongs to, next to its Hopfile
+    pub fn project_path() -> Option<PathBuf> {
+        let hopfile = HopFile::find_path(current_dir().ok()?)?;
+
+        Some(hopfile.parent()?.join(CONFIG_PATH))
+    }
+
+    /// Loads the user config, with the project config on top of it
+    pub async fn load() -> Result<Self> {
+        let mut table = read_table(&Self::user_path()?).await?;
+        let mut ignored_keys = vec![];
+
+        if let Some(path) = Self::project_path() {
+            let mut project = read_table(&path).await?;
+            let trusted = table.get("trust_project_config") == Some(&Value::Boolean(true));
+
+            ignored_keys = untrust(&mut project, trusted);
+
+            merge(&mut table, project);
+        }
+
+        let mut config = Self::from_table(table)
+            .map_err(|e| anyhow!("{e}, run `{EXEC_NAME} config edit` to fix it"))?;
+        config.ignored_keys = ignored_keys;
+
+        Ok(config)
+    }
+
+    pub fn from_table(table: Table) -> Result<Self> {
+        Value::Table(table)
+            .try_into()
+            .map_err(|e| anyhow!("Invalid config: {}", e.to_string().trim_end()))
+    }
+
+    pub fn to_table(&self) -> Result<Table> {
+        Table::try_from(self).context("Failed to serialize config")
+    }
+
+    pub fn editor(&self) -> String {
+        self.editor
+            .clone()
+            .or_else(|| std::env::var("EDITOR").ok())
+            .or_else(|| std::env::var("VISUAL").ok())
+            .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+    }
+
+    /// Whether output should be colored, `auto` respects `NO_COLOR` and disables colors when not in a terminal
+    pub fn colors_enabled(&self) -> bool {
+        match self.color.unwrap_or_default() {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal()
+            }
+        }
+    }
+}
+
+/// Removes the keys a project config cannot set, returns the ones it tried to
+fn untrust(project: &mut Table, trusted: bool) -> Vec<String> {
+    let mut ignored = vec![];
+
+    // a project cannot trust itself
+    if project.remove("trust_project_config").is_some() {
+        ignored.push("trust_project_config".to_string());
+    }
+
+    if !trusted {
+        for key in TRUSTED_KEYS {
+            if project.remove(*key).is_some() {
+                ignored.push(key.to_string());
+            }
+        }
+    }
+
+    ignored
+}
+
+pub async fn read_table(path: &PathBuf) -> Result<Table> {
+    if fs::metadata(path).await.is_err() {
+        return Ok(Table::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read `{}`", path.display()))?;
+
+    content
+        .parse::<Table>()
+        .with_context(|| format!("Failed to parse `{}`", path.display()))
+}
+
+pub async fn write_table(path: &PathBuf, table: &Table) -> Result<()> {
+    fs::create_dir_all(path.parent().context("Failed to get config directory")?)
+        .await
+        .context("Failed to create config directory")?;
+
+    fs::write(path, toml::to_string_pretty(table)?)
+        .await
+        .with_context(|| format!("Failed to write `{}`", path.display()))
+}
+
+/// Recursively copies the values of `other` into `base`
+pub fn merge(base: &mut Table, other: Table) {
+    for (key, value) in other {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(other)) => merge(base, other),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Reads a dotted key like `logs.lines`
+pub fn get_key<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let (path, last) = split_key(key);
+
+    let mut current = table;
+
+    for part in path {
+        current = current.get(part)?.as_table()?;
+    }
+
+    current.get(last)
+}
+
+/// Writes a dotted key like `logs.lines`, creating the tables in between
+pub fn set_key(table: &mut Table, key: &str, value: Value) -> Result<()> {
+    let (path, last) = split_key(key);
+
+    let mut current = table;
+
+    for part in path {
+        let entry = current
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+
+        let Value::Table(next) = entry else {
+            bail!("`{part}` is not a section of the config");
+        };
+
+        current = next;
+    }
+
+    current.insert(last.to_string(), value);
+
+    Ok(())
+}
+
+fn split_key(key: &str) -> (Vec<&str>, &str) {
+    let mut parts = key.split('.').collect::<Vec<_>>();
+    let last = parts.pop().unwrap_or_default();
+
+    (parts, last)
+}
+
+/// Parses a value from the command line, anything that is not valid TOML is a string
+pub fn parse_value(value: &str) -> Value {
+    format!("value = {value}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(value.to_string()))
+}
+
+/// Flattens the config into dotted keys and their values
+pub fn flatten(table: &Table) -> Vec<(String, Value)> {
+    let mut values = vec![];
+
+    for (key, value) in table {
+        match value {
+            Value::Table(inner) => values.extend(
+                flatten(inner)
+                    .into_iter()
+                    .map(|(inner_key, value)| (format!("{key}.{inner_key}"), value)),
+            ),
+
+            value => values.push((key.clone(), value.clone())),
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge_project_over_user() {
+        let mut user = "output = \"json\"\n[logs]\nlines = 20\ntimestamps = true"
+            .parse::<Table>()
+            .unwrap();
+        let project = "[logs]\nlines = 50".parse::<Table>().unwrap();
+
+        merge(&mut user, project);
+
+        let config = Config::from_table(user).unwrap();
+
+        assert_eq!(config.output, Some(OutputFormat::Json));
+        assert_eq!(config.logs.lines, Some(50));
+        assert_eq!(config.logs.timestamps, Some(true));
+    }
+
+    #[test]
+    fn test_set_and_get_key() {
+        let mut table = Table::new();
+
+        set_key(&mut table, "deploy.mode", parse_value("local")).unwrap();
+        set_key(&mut table, "logs.lines", parse_value("25")).unwrap();
+
+        assert_eq!(
+            get_key(&table, "deploy.mode"),
+            Some(&Value::String("local".to_string()))
+        );
+        assert_eq!(get_key(&table, "logs.lines"), Some(&Value::Integer(25)));
+        assert!(set_key(&mut table, "logs.lines.max", parse_value("1")).is_err());
+
+        let config = Config::from_table(table).unwrap();
+
+        assert_eq!(config.deploy.mode, Some(DeployMode::Local));
+    }
+
+    #[test]
+    fn test_max_upload_size() {
+        let mut config = DeployConfig::default();
+
+        assert_eq!(config.max_upload_size().unwrap(), DEFAULT_MAX_UPLOAD_SIZE);
+
+        config.max_upload_size = Some("500MB".to_string());
+        assert_eq!(config.max_upload_size().unwrap(), 500 * 1024 * 1024);
+
+        config.max_upload_size = Some("lots".to_string());
+        assert!(config.max_upload_size().is_err());
+    }
+
+    #[test]
+    fn test_untrusted_project_config() {
+        let content = "editor = \"rm -rf\"\nca_cert = \"evil.pem\"\ntrust_project_config = true\n[logs]\nlines = 50";
+
+        let mut project = content.parse::<Table>().unwrap();
+
+        assert_eq!(
+            untrust(&mut project, false),
+            vec!["trust_project_config", "ca_cert", "editor"]
+        );
+        assert_eq!(project.keys().collect::<Vec<_>>(), vec!["logs"]);
+
+        let mut project = content.parse::<Table>().unwrap();
+
+        assert_eq!(untrust(&mut project, true), vec!["trust_project_config"]);
+        assert!(project.contains_key("editor"));
+    }
+
+    #[test]
+    fn test_unknown_key_is_invalid() {
+        let mut table = Table::new();
+
+        set_key(&mut table, "logs.colour", parse_value("true")).unwrap();
+
+        assert!(Config::from_table(table).is_err());
+    }
+}
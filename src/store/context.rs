@@ -1,6 +1,7 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use anyhow::{anyhow, Context as _, Result};
+use anyhow::{anyhow, ensure, Context as _, Result};
 use serde::{Deserialize, Serialize};
 use tokio::fs::{self, File};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -12,26 +13,110 @@ use crate::commands::projects::types::Project;
 use crate::config::EXEC_NAME;
 use crate::impl_store;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub const DEFAULT_CONTEXT: &str = "default";
+
+/// A named bundle of user, project and api url
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NamedContext {
+    pub user: Option<String>,
+    pub project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+/// On disk format of the context store
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ContextFile {
+    current_context: Option<String>,
+    #[serde(default)]
+    contexts: BTreeMap<String, NamedContext>,
+    last_version_check: Option<(String, String)>,
+
+    // single context format used by older versions, only read to migrate it
+    #[serde(default, skip_serializing)]
+    default_project: Option<String>,
+    #[serde(default, skip_serializing)]
+    default_user: Option<String>,
+    #[serde(default, skip_serializing)]
+    override_api_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "ContextFile", into = "ContextFile")]
 pub struct Context {
-    /// stored in the context store file
+    /// project of the selected context
     pub default_project: Option<String>,
-    /// stored in the context store file
+    /// user of the selected context
     pub default_user: Option<String>,
-    /// api url override, only save if its not null
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// api url override of the selected context
     pub override_api_url: Option<String>,
     // latest version of the cli and time it was last checked
     pub last_version_check: Option<(String, String)>,
+    /// name of the context that is used by default
+    pub current_context: String,
+    /// all contexts, the selected one is only updated on save
+    pub contexts: BTreeMap<String, NamedContext>,
 
     /// runtime context
-    #[serde(skip)]
+    pub selected_context: String,
+    /// runtime context
     pub current: Option<AuthorizedClient>,
     /// runtime context
-    #[serde(skip)]
     pub project_override: Option<String>,
 }
 
+impl Default for Context {
+    fn default() -> Self {
+        ContextFile::default().into()
+    }
+}
+
+impl From<ContextFile> for Context {
+    fn from(mut file: ContextFile) -> Self {
+        // migrate the old single context format
+        if file.contexts.is_empty() {
+            file.contexts.insert(
+                DEFAULT_CONTEXT.to_string(),
+                NamedContext {
+                    user: file.default_user,
+                    project: file.default_project,
+                    api_url: file.override_api_url,
+                },
+            );
+        }
+
+        let current_context = file
+            .current_context
+            .filter(|name| file.contexts.contains_key(name))
+            .unwrap_or_else(|| file.contexts.keys().next().unwrap().clone());
+
+        let selected = file.contexts[&current_context].clone();
+
+        Self {
+            default_project: selected.project,
+            default_user: selected.user,
+            override_api_url: selected.api_url,
+            last_version_check: file.last_version_check,
+            selected_context: current_context.clone(),
+            current_context,
+            contexts: file.contexts,
+            current: None,
+            project_override: None,
+        }
+    }
+}
+
+impl From<Context> for ContextFile {
+    fn from(ctx: Context) -> Self {
+        Self {
+            contexts: ctx.all_contexts(),
+            current_context: Some(ctx.current_context),
+            last_version_check: ctx.last_version_check,
+            ..Default::default()
+        }
+    }
+}
+
 impl Storable for Context {
     fn path() -> Result<PathBuf> {
         home_path(".hop/context.json")
@@ -64,6 +149,90 @@ impl Context {
         }
     }
 
+    /// All contexts including the unsaved changes to the selected one
+    pub fn all_contexts(&self) -> BTreeMap<String, NamedContext> {
+        let mut contexts = self.contexts.clone();
+
+        contexts.insert(
+            self.selected_context.clone(),
+            NamedContext {
+                user: self.default_user.clone(),
+                project: self.default_project.clone(),
+                api_url: self.override_api_url.clone(),
+            },
+        );
+
+        contexts
+    }
+
+    /// Select a context for this invocation, does not change the default one
+    pub fn select_context(&mut self, name: &str) -> Result<()> {
+        self.contexts = self.all_contexts();
+
+        let selected = self.contexts.get(name).cloned().with_context(|| {
+            format!(
+                "Context `{name}` does not exist, run `{EXEC_NAME} context ls` to see all contexts"
+            )
+        })?;
+
+        self.selected_context = name.to_string();
+        self.default_project = selected.project;
+        self.default_user = selected.user;
+        self.override_api_url = selected.api_url;
+
+        Ok(())
+    }
+
+    pub fn create_context(&mut self, name: &str, context: NamedContext) -> Result<()> {
+        ensure!(
+            !self.contexts.contains_key(name),
+            "Context `{name}` already exists"
+        );
+
+        self.contexts.insert(name.to_string(), context);
+
+        Ok(())
+    }
+
+    pub fn remove_context(&mut self, name: &str) -> Result<()> {
+        ensure!(
+            self.contexts.contains_key(name),
+            "Context `{name}` does not exist"
+        );
+        ensure!(
+            name != self.current_context && name != self.selected_context,
+            "Cannot remove the context in use, switch to another one with `{EXEC_NAME} context use` first"
+        );
+
+        self.contexts.remove(name);
+
+        Ok(())
+    }
+
+    pub fn rename_context(&mut self, name: &str, new_name: &str) -> Result<()> {
+        ensure!(
+            !self.contexts.contains_key(new_name),
+            "Context `{new_name}` already exists"
+        );
+
+        let context = self
+            .contexts
+            .remove(name)
+            .with_context(|| format!("Context `{name}` does not exist"))?;
+
+        self.contexts.insert(new_name.to_string(), context);
+
+        if self.current_context == name {
+            self.current_context = new_name.to_string();
+        }
+
+        if self.selected_context == name {
+            self.selected_context = new_name.to_string();
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub fn current_project_error(&self) -> Result<Project> {
         self.current_project().with_context(|| anyhow!("No project specified, run `{EXEC_NAME} projects switch` or use --project to specify a project"))
@@ -75,3 +244,55 @@ impl Context {
         format!("{EXEC_NAME} update")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_migrate_single_context() {
+        let ctx: Context = serde_json::from_str(
+            r#"{"default_project":"project_1","default_user":"user_1","last_version_check":null}"#,
+        )
+        .unwrap();
+
+        assert_eq!(ctx.current_context, DEFAULT_CONTEXT);
+        assert_eq!(ctx.default_project.as_deref(), Some("project_1"));
+        assert_eq!(ctx.default_user.as_deref(), Some("user_1"));
+
+        let saved = serde_json::to_value(&ctx).unwrap();
+
+        assert_eq!(saved["current_context"], DEFAULT_CONTEXT);
+        assert_eq!(saved["contexts"][DEFAULT_CONTEXT]["project"], "project_1");
+        assert!(saved.get("default_project").is_none());
+    }
+
+    #[test]
+    fn test_select_context() {
+        let mut ctx = Context::default();
+
+        ctx.default_project = Some("production".to_string());
+        ctx.create_context(
+            "staging",
+            NamedContext {
+                project: Some("staging".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        ctx.select_context("staging").unwrap();
+        ctx.default_project = Some("staging_2".to_string());
+
+        let contexts = ctx.all_contexts();
+
+        assert_eq!(ctx.current_context, DEFAULT_CONTEXT);
+        assert_eq!(
+            contexts[DEFAULT_CONTEXT].project.as_deref(),
+            Some("production")
+        );
+        assert_eq!(contexts["staging"].project.as_deref(), Some("staging_2"));
+        assert!(ctx.select_context("missing").is_err());
+        assert!(ctx.remove_context("staging").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use argon2::Argon2;
+use async_trait::async_trait;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use super::utils::home_path;
+use crate::errors::{ErrorKind, HopError};
+use crate::utils::prompt;
+
+const KEYRING_SERVICE: &str = "hop-cli";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    #[default]
+    Plaintext,
+    Keyring,
+    EncryptedFile,
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_str(&format!("\"{}\"", s.to_lowercase())).map_err(|e| anyhow!(e))
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).unwrap().replace('"', "")
+        )
+    }
+}
+
+impl Backend {
+    /// The keyring if the platform has one, the plaintext file otherwise
+    pub async fn preferred() -> Self {
+        if KeyringStore::is_available().await {
+            Self::Keyring
+        } else {
+            Self::Plaintext
+        }
+    }
+
+    pub async fn open(self) -> Result<Box<dyn CredentialStore>> {
+        Ok(match self {
+            Self::Plaintext => Box::<MemoryStore>::default(),
+            Self::Keyring => Box::new(KeyringStore),
+            Self::EncryptedFile => Box::new(EncryptedFileStore::open().await?),
+        })
+    }
+}
+
+/// Storage of the tokens of authorized users
+#[async_trait]
+pub trait CredentialStore: Debug + Send + Sync {
+    async fn get(&self, user_id: &str) -> Result<Option<String>>;
+    async fn set(&self, user_id: &str, token: &str) -> Result<()>;
+    async fn delete(&self, user_id: &str) -> Result<()>;
+}
+
+/// Keeps tokens in memory, used for the plaintext
+/// backend which is persisted with the auth store itself
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    tokens: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryStore {
+    pub fn new(tokens: HashMap<String, String>) -> Self {
+        Self {
+            tokens: Mutex::new(tokens),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialStore for MemoryStore {
+    async fn get(&self, user_id: &str) -> Result<Option<String>> {
+        Ok(self.tokens.lock().unwrap().get(user_id).cloned())
+    }
+
+    async fn set(&self, user_id: &str, token: &str) -> Result<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), token.to_string());
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        self.tokens.lock().unwrap().remove(user_id);
+
+        Ok(())
+    }
+}
+
+/// Secret Service, Keychain or Credential Manager depending on the platform
+#[derive(Debug)]
+pub struct KeyringStore;
+
+impl KeyringStore {
+    async fn is_available() -> bool {
+        // a missing entry means the keyring itself could be reached
+        matches!(
+            Self::run("availability-check", |entry| entry.get_password()).await,
+            Err(keyring::Error::NoEntry) | Ok(_)
+        )
+    }
+
+    // the keyring apis are blocking, so keep them off the async runtime
+    async fn run<T, F>(user_id: &str, f: F) -> keyring::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(keyring::Entry) -> keyring::Result<T> + Send + 'static,
+    {
+        let user_id = user_id.to_string();
+
+        tokio::task::spawn_blocking(move || f(keyring::Entry::new(KEYRING_SERVICE, &user_id)?))
+            .await
+            .map_err(|e| keyring::Error::PlatformFailure(Box::new(e)))?
+    }
+}
+
+#[async_trait]
+impl CredentialStore for KeyringStore {
+    async fn get(&self, user_id: &str) -> Result<Option<String>> {
+        match Self::run(user_id, |entry| entry.get_password()).await {
+            Ok(token) => Ok(Some(token)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(error) => Err(error).context("Failed to read token from the keyring"),
+        }
+    }
+
+    async fn set(&self, user_id: &str, token: &str) -> Result<()> {
+        let token = token.to_string();
+
+        Self::run(user_id, move |entry| entry.set_password(&token))
+            .await
+            .context("Failed to write token to the keyring")
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        match Self::run(user_id, |entry| entry.delete_password()).await {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(error) => Err(error).context("Failed to delete token from the keyring"),
+        }
+    }
+}
+
+/// Tokens encrypted with a key derived from a passphrase,
+/// the passphrase is read from `HOP_PASSPHRASE` or prompted for
+#[derive(Debug)]
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: String,
+    tokens: Mutex<HashMap<String, String>>,
+}
+
+impl EncryptedFileStore {
+    pub async fn open() -> Result<Self> {
+        let path = home_path(".hop/auth.enc")?;
+
+        let passphrase = match std::env::var("HOP_PASSPHRASE") {
+            Ok(passphrase) => passphrase,
+
+            Err(_) => {
+                ensure!(
+                    prompt::is_interactive(),
+                    HopError::new(
+                        ErrorKind::Auth,
+                        "The credentials file is encrypted, set `HOP_PASSPHRASE` to its passphrase to use it in non-interactive mode"
+                    )
+                );
+
+                prompt::Password::new("HOP_PASSPHRASE")
+                    .with_prompt("Passphrase of the credentials file")
+                    .interact()?
+            }
+        };
+
+        Self::open_at(path, passphrase).await
+    }
+
+    pub async fn open_at(path: PathBuf, passphrase: String) -> Result<Self> {
+        let tokens = if fs::metadata(&path).await.is_ok() {
+            decrypt(&fs::read(&path).await?, &passphrase)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            passphrase,
+            tokens: Mutex::new(tokens),
+        })
+    }
+
+    async fn write(&self) -> Result<()> {
+        let data = encrypt(&self.tokens.lock().unwrap(), &self.passphrase)?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("Failed to create store directory")?;
+        }
+
+        fs::write(&self.path, data)
+            .await
+            .context("Failed to write credentials file")?;
+
+        // only the current user should be able to read the file
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CredentialStore for EncryptedFileStore {
+    async fn get(&self, user_id: &str) -> Result<Option<String>> {
+        Ok(self.tokens.lock().unwrap().get(user_id).cloned())
+    }
+
+    async fn set(&self, user_id: &str, token: &str) -> Result<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), token.to_string());
+
+        self.write().await
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        self.tokens.lock().unwrap().remove(user_id);
+
+        self.write().await
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive key: {e}"))?;
+
+    Ok(key)
+}
+
+/// Layout of the file is `salt | nonce | ciphertext`
+fn encrypt(tokens: &HashMap<String, String>, passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, serde_json::to_vec(tokens)?.as_slice())
+        .map_err(|_| anyhow!("Failed to encrypt credentials"))?;
+
+    Ok([salt.as_slice(), nonce.as_slice(), &ciphertext].concat())
+}
+
+fn decrypt(data: &[u8], passphrase: &str) -> Result<HashMap<String, String>> {
+    ensure!(
+        data.len() > SALT_LENGTH + NONCE_LENGTH,
+        "Credentials file is corrupted"
+    );
+
+    let (salt, rest) = data.split_at(SALT_LENGTH);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LENGTH);
+
+    let plaintext = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Failed to decrypt credentials, is the passphrase correct?"))?;
+
+    serde_json::from_slice(&plaintext).context("Failed to deserialize credentials")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let tokens = HashMap::from([("user_1".to_string(), "pat_1".to_string())]);
+
+        let data = encrypt(&tokens, "hunter2").unwrap();
+
+        assert_eq!(decrypt(&data, "hunter2").unwrap(), tokens);
+        assert!(decrypt(&data, "hunter3").is_err());
+    }
+}
@@ -1,11 +1,24 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env::current_dir;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tokio::fs::{self, File};
 use tokio::io::AsyncWriteExt;
 
+use crate::commands::gateways::types::{Gateway, GatewayConfig};
+use crate::commands::ignite::health::types::CreateHealthCheck;
+use crate::commands::ignite::types::CreateDeployment;
+use crate::commands::ignite::utils::env_file_to_map;
+use crate::config::EXEC_NAME;
+use crate::utils::interpolate::interpolate;
+
 pub static VALID_HOP_FILENAMES: &[&str] = &[
     "hop.yml",
     "hop.yaml",
@@ -16,32 +29,207 @@ pub static VALID_HOP_FILENAMES: &[&str] = &[
     ".hoprc.json",
 ];
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// Environment whose overrides are applied to every hopfile that is found
+static ENVIRONMENT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Selects the environment of the hopfiles, e.g. from `--environment` or `HOP_ENV`
+pub fn set_environment(environment: Option<String>) {
+    if let Ok(mut selected) = ENVIRONMENT.lock() {
+        *selected = environment;
+    }
+}
+
+pub fn environment() -> Option<String> {
+    ENVIRONMENT.lock().ok()?.clone()
+}
+
+/// Latest version of the hopfile format, version 1 only links a deployment
+/// while version 2 can also describe it, see [`DeploymentSpec`]
+pub const LATEST_VERSION: u8 = 2;
+
+/// Links the directory to a deployment or describes it, see `hop validate --schema`
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+// unknown fields are rejected so typos do not go unnoticed
+#[serde(deny_unknown_fields)]
 pub struct HopFile {
+    /// Editors use it to find the JSON Schema of the hopfile
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
     pub version: u8,
+    #[serde(default, skip_serializing_if = "HopFileConfig::is_empty")]
     pub config: HopFileConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<DeploymentSpec>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub services: BTreeMap<String, ServiceSpec>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, Environment>,
+    /// Environment whose overrides were applied when the hopfile was found
+    #[serde(skip)]
+    pub environment: Option<String>,
     #[serde(skip)]
     pub path: PathBuf,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct HopFileConfig {
+    /// Can be left out in version 2 to use the current project
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub project_id: String,
+    /// Can be left out in version 2 to find the deployment by its name
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub deployment_id: String,
 }
 
+impl HopFileConfig {
+    pub fn is_empty(&self) -> bool {
+        self.project_id.is_empty() && self.deployment_id.is_empty()
+    }
+}
+
+/// Overrides of a named environment, like `staging` or `production`
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Environment {
+    #[serde(default, skip_serializing_if = "HopFileConfig::is_empty")]
+    pub config: HopFileConfig,
+    /// Fields of the `deployment` that are replaced, maps like `env` are merged
+    /// and fields set to `null` are removed
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub deployment: Map<String, Value>,
+}
+
+/// Desired state of a deployment, applied with `hop apply`
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeploymentSpec {
+    #[serde(flatten)]
+    pub config: CreateDeployment,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub containers: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gateways: Vec<GatewaySpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub health_checks: Vec<CreateHealthCheck>,
+    /// Keys written in the hopfile, the defaults of `config` are
+    /// indistinguishable from values that were left out
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub fields: BTreeSet<String>,
+}
+
+impl DeploymentSpec {
+    /// Whether the hopfile sets the key, like `env`
+    pub fn has(&self, field: &str) -> bool {
+        self.fields.contains(field)
+    }
+}
+
+/// A Gateway of the deployment with the domains attached to it
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GatewaySpec {
+    #[serde(flatten)]
+    pub config: GatewayConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<String>,
+}
+
+impl GatewaySpec {
+    pub fn from_gateway(gateway: &Gateway) -> Self {
+        let mut domains = gateway
+            .domains
+            .iter()
+            .map(|domain| domain.domain.clone())
+            .collect::<Vec<_>>();
+
+        domains.sort();
+
+        Self {
+            config: GatewayConfig::from_gateway(gateway),
+            domains,
+        }
+    }
+}
+
+/// A service of a monorepo, deployed with `hop deploy --all` or `hop deploy <service>`
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceSpec {
+    /// Directory of the service relative to the hopfile, it has its own hopfile
+    pub path: PathBuf,
+    /// Services that are deployed before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
 impl HopFile {
     pub fn new(path: PathBuf, project: &str, deployment: &str) -> HopFile {
         HopFile {
+            schema: None,
             version: 1,
             config: HopFileConfig {
                 project_id: project.to_string(),
                 deployment_id: deployment.to_string(),
             },
+            deployment: None,
+            services: BTreeMap::new(),
+            environments: BTreeMap::new(),
+            environment: None,
             path,
         }
     }
 
+    /// Links the deployment to the directory, in the selected environment if there is one.
+    /// The rest of an existing hopfile, like its deployment and other environments, is kept
+    pub async fn link(dir: &Path, project: &str, deployment: &str) -> Result<Self> {
+        let existing = VALID_HOP_FILENAMES
+            .iter()
+            .map(|filename| dir.join(filename))
+            .find(|path| path.exists());
+
+        let hopfile = match existing {
+            Some(path) => {
+                let content = fs::read_to_string(&path).await?;
+
+                Self::parse::<Self>(&path, &content)?.with_path(path)
+            }
+
+            None => Self::new(dir.join("hop.yml"), "", ""),
+        };
+
+        hopfile
+            .relink(environment(), project, deployment)
+            .save()
+            .await
+    }
+
+    /// Points the hopfile or its environment at the deployment, everything else is kept
+    fn relink(mut self, environment: Option<String>, project: &str, deployment: &str) -> Self {
+        let config = HopFileConfig {
+            project_id: project.to_string(),
+            deployment_id: deployment.to_string(),
+        };
+
+        match environment {
+            Some(environment) => {
+                // environments are only supported since version 2
+                self.version = LATEST_VERSION;
+                self.environments.entry(environment).or_default().config = config;
+            }
+
+            None => self.config = config,
+        }
+
+        self
+    }
+
+    fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = path;
+        self
+    }
+
     fn serialize(path: PathBuf, content: Self) -> Option<String> {
         match path.extension() {
             Some(ext) => match ext.to_str() {
@@ -61,67 +249,322 @@ impl HopFile {
         }
     }
 
-    fn deserialize(path: PathBuf, content: &str) -> Option<Self> {
-        let hopfile: Option<Self> = match path.extension() {
-            Some(ext) => match ext.to_str() {
-                Some("yml" | "yaml") => serde_yaml::from_str(content).ok(),
-                Some("json") => serde_json::from_str(content).ok(),
-                _ => None,
-            },
-            None => {
-                if let Ok(s) = serde_yaml::from_str(content) {
-                    Some(s)
-                } else if let Ok(s) = serde_json::from_str(content) {
-                    Some(s)
-                } else {
-                    None
-                }
-            }
+    /// Parses the content without checking it, the format is picked by the extension.
+    /// Errors include the line and column
+    pub fn parse<T: DeserializeOwned>(path: &Path, content: &str) -> Result<T> {
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(content).map_err(anyhow::Error::from),
+            // JSON is valid YAML too
+            _ => serde_yaml::from_str(content).map_err(anyhow::Error::from),
         };
 
-        hopfile.map(|mut hopfile| {
-            hopfile.path = path;
-            hopfile
+        parsed.map_err(|error| anyhow!("Invalid hopfile {}: {error}", path.display()))
+    }
+
+    /// Parses the content and replaces the `${VAR}` variables of its string values,
+    /// after parsing so their values cannot change the structure of the document
+    pub fn resolve(
+        path: &Path,
+        content: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<Value> {
+        let mut value = Self::parse::<Value>(path, content)?;
+
+        interpolate_values(&mut value, "", &|name| variables.get(name).cloned())
+            .map_err(|error| anyhow!("Invalid hopfile {}: {error}", path.display()))?;
+
+        Self::coerce(&mut value)?;
+
+        Ok(value)
+    }
+
+    /// Variables are replaced with strings, fields that are numbers or booleans
+    /// in the schema get their value back like YAML would have parsed it
+    fn coerce(value: &mut Value) -> Result<()> {
+        let schema = serde_json::to_value(Self::json_schema())?;
+
+        coerce_values(value, &[&schema], &schema);
+
+        Ok(())
+    }
+
+    /// Like `parse` with the variables replaced
+    pub fn parse_resolved<T: DeserializeOwned>(
+        path: &Path,
+        content: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<T> {
+        Self::from_resolved(path, content, Self::resolve(path, content, variables)?)
+    }
+
+    fn from_resolved<T: DeserializeOwned>(path: &Path, content: &str, value: Value) -> Result<T> {
+        serde_json::from_value(value).map_err(|error| {
+            // the same error in the original text has its line and column
+            Self::parse::<T>(path, content)
+                .err()
+                .unwrap_or_else(|| anyhow!("Invalid hopfile {}: {error}", path.display()))
         })
     }
 
+    #[cfg(test)]
+    fn deserialize(path: PathBuf, content: &str) -> Result<Self> {
+        Self::deserialize_environment(path, content, &HashMap::new(), None)
+    }
+
+    /// Parses and validates the hopfile with its variables replaced
+    /// and the overrides of the environment applied to it
+    pub fn deserialize_environment(
+        path: PathBuf,
+        content: &str,
+        variables: &HashMap<String, String>,
+        environment: Option<&str>,
+    ) -> Result<Self> {
+        let mut value = Self::resolve(&path, content, variables)?;
+        let mut hopfile = Self::from_resolved::<Self>(&path, content, value.clone())?;
+
+        if let Some(name) = environment {
+            let Some(overrides) = hopfile.environments.get(name) else {
+                bail!(
+                    "{} has no `{name}` environment, add it to `environments` or link a deployment to it with `{EXEC_NAME} link --environment {name}`",
+                    path.display()
+                );
+            };
+
+            // the overrides are merged before parsing, so only the fields that
+            // are in the file are overridden and not their defaults
+            if !overrides.config.is_empty() {
+                merge_values(
+                    &mut value["config"],
+                    serde_json::to_value(&overrides.config)?,
+                );
+            }
+
+            if !overrides.deployment.is_empty() {
+                merge_values(
+                    &mut value["deployment"],
+                    Value::Object(overrides.deployment.clone()),
+                );
+            }
+
+            // the overrides have no schema of their own
+            Self::coerce(&mut value)?;
+
+            hopfile = serde_json::from_value(value.clone()).map_err(|error| {
+                anyhow!(
+                    "Invalid `{name}` environment in {}: {error}",
+                    path.display()
+                )
+            })?;
+            hopfile.environment = Some(name.to_string());
+        }
+
+        if let (Some(spec), Some(Value::Object(fields))) =
+            (hopfile.deployment.as_mut(), value.get("deployment"))
+        {
+            spec.fields = fields.keys().cloned().collect();
+        }
+
+        hopfile
+            .validate()
+            .map_err(|error| anyhow!("Invalid hopfile {}: {error}", path.display()))?;
+
+        Ok(hopfile.with_path(path))
+    }
+
+    /// Checks what the types cannot, version 1 files always link a deployment and
+    /// only version 2 can describe one, list the services of a monorepo or have environments
+    pub fn validate(&self) -> Result<()> {
+        match self.version {
+            1 => {
+                ensure!(
+                    !self.config.project_id.is_empty() && !self.config.deployment_id.is_empty(),
+                    "`config` needs a `project_id` and a `deployment_id`"
+                );
+                ensure!(
+                    self.deployment.is_none()
+                        && self.services.is_empty()
+                        && self.environments.is_empty(),
+                    "`deployment`, `services` and `environments` need `version: {LATEST_VERSION}`"
+                );
+            }
+
+            LATEST_VERSION => {
+                for (name, service) in &self.services {
+                    for dependency in &service.depends_on {
+                        ensure!(
+                            self.services.contains_key(dependency),
+                            "service `{name}` depends on `{dependency}` which is not in `services`"
+                        );
+                    }
+                }
+
+                if self.deployment_ref().is_none() && self.services.is_empty() {
+                    match &self.environment {
+                        Some(name) => bail!(
+                            "the `{name}` environment does not set `config.deployment_id` or `deployment.name`"
+                        ),
+
+                        None if !self.environments.is_empty() => bail!(
+                            "select one of the environments {} with `--environment` or `HOP_ENV`",
+                            self.environments
+                                .keys()
+                                .map(|name| format!("`{name}`"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+
+                        None => bail!(
+                            "set `config.deployment_id` or `deployment.name`, or list `services`"
+                        ),
+                    }
+                }
+            }
+
+            version => bail!("unsupported version {version}, the latest is {LATEST_VERSION}"),
+        }
+
+        Ok(())
+    }
+
+    /// JSON Schema of the hopfile, published as `hopfile.schema.json`
+    pub fn json_schema() -> RootSchema {
+        schema_for!(HopFile)
+    }
+
+    /// Reference to the deployment, its ID or the name from the spec
+    pub fn deployment_ref(&self) -> Option<&str> {
+        if self.config.deployment_id.is_empty() {
+            self.deployment.as_ref()?.config.name.as_deref()
+        } else {
+            Some(&self.config.deployment_id)
+        }
+    }
+
+    /// Directory of a service, relative paths start at the hopfile
+    pub fn service_dir(&self, service: &ServiceSpec) -> PathBuf {
+        self.path
+            .parent()
+            .map(|dir| dir.join(&service.path))
+            .unwrap_or_else(|| service.path.clone())
+    }
+
     // Find a hopfile in the current directory or any of its parents.
-    pub async fn find(mut path: PathBuf) -> Option<Self> {
+    pub async fn find(path: PathBuf) -> Result<Option<Self>> {
         // if there are environment variables set, use them instead
         if let (Ok(project_id), Ok(deployment_id)) =
             (std::env::var("PROJECT_ID"), std::env::var("DEPLOYMENT_ID"))
         {
             log::info!("Using environment variables for project and deployment IDs");
 
-            return Some(HopFile::new(
+            return Ok(Some(HopFile::new(
                 path.join("hop.yml"),
                 &project_id,
                 &deployment_id,
-            ));
+            )));
+        }
+
+        let Some(file_path) = Self::find_path(path) else {
+            return Ok(None);
+        };
+
+        let content = Self::read(&file_path).await?;
+        let variables = Self::variables(&file_path).await?;
+
+        let hopfile = Self::deserialize_environment(
+            file_path,
+            &content,
+            &variables,
+            environment().as_deref(),
+        )?;
+
+        if let Some(name) = &hopfile.environment {
+            log::debug!("Using the `{name}` environment of the hopfile");
         }
 
+        Ok(Some(hopfile))
+    }
+
+    /// Closest hopfile that lists `services`, skipping the hopfiles of the services
+    pub async fn find_services(mut path: PathBuf) -> Result<Option<Self>> {
+        loop {
+            let Some(file_path) = Self::find_path(path) else {
+                return Ok(None);
+            };
+
+            let content = Self::read(&file_path).await?;
+            let variables = Self::variables(&file_path).await?;
+            let hopfile = Self::parse_resolved::<Self>(&file_path, &content, &variables)?;
+
+            if !hopfile.services.is_empty() {
+                hopfile.validate()?;
+
+                return Ok(Some(hopfile.with_path(file_path)));
+            }
+
+            // continue above the directory of the hopfile
+            let Some(parent) = file_path.parent().and_then(Path::parent) else {
+                return Ok(None);
+            };
+
+            path = parent.to_path_buf();
+        }
+    }
+
+    /// Content of the hopfile, its variables are replaced after parsing it
+    pub async fn read(path: &Path) -> Result<String> {
+        fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Could not read {}", path.display()))
+    }
+
+    /// Variables for the `${VAR}` of the hopfile, they are taken from
+    /// the environment or the `.env` file next to the hopfile
+    pub async fn variables(path: &Path) -> Result<HashMap<String, String>> {
+        let mut variables = HashMap::new();
+
+        if let Some(env_file) = path
+            .parent()
+            .map(|dir| dir.join(".env"))
+            .filter(|env_file| env_file.exists())
+        {
+            variables.extend(env_file_to_map(env_file).await?);
+        }
+
+        variables.extend(std::env::vars());
+
+        Ok(variables)
+    }
+
+    /// The hopfile at `path`, or the closest one if it is a directory
+    pub fn find_file(path: PathBuf) -> Result<PathBuf> {
+        if !path.is_dir() {
+            return Ok(path);
+        }
+
+        Self::find_path(path.clone())
+            .with_context(|| format!("No hopfile found in {}", path.display()))
+    }
+
+    /// Path of the closest hopfile in the directory or any of its parents
+    pub fn find_path(mut path: PathBuf) -> Option<PathBuf> {
         loop {
             for filename in VALID_HOP_FILENAMES {
                 let file_path = path.join(filename);
 
                 if file_path.exists() {
-                    let content = fs::read_to_string(file_path.clone()).await.ok()?;
-
-                    return Self::deserialize(file_path, &content);
+                    return Some(file_path);
                 }
             }
 
             if !path.pop() {
-                break;
+                return None;
             }
         }
-
-        None
     }
 
     #[inline]
-    pub async fn find_current() -> Option<Self> {
-        Self::find(current_dir().ok()?).await
+    pub async fn find_current() -> Result<Option<Self>> {
+        Self::find(current_dir()?).await
     }
 
     pub async fn save(self) -> Result<Self> {
@@ -139,3 +582,444 @@ impl HopFile {
         Ok(self)
     }
 }
+
+/// Merges `overrides` into `base`, objects are merged key by key
+/// and `null` removes the key
+fn merge_values(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                if value.is_null() {
+                    base.remove(&key);
+                } else {
+                    merge_values(base.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// Replaces the variables of every string in `value`, errors name the key of the string
+fn interpolate_values(
+    value: &mut Value,
+    key: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match value {
+        Value::String(string) => {
+            *string = interpolate(string, lookup).map_err(|error| anyhow!("`{key}`: {error}"))?;
+        }
+
+        Value::Array(values) => {
+            for (idx, value) in values.iter_mut().enumerate() {
+                interpolate_values(value, &format!("{key}[{idx}]"), lookup)?;
+            }
+        }
+
+        Value::Object(values) => {
+            for (name, value) in values.iter_mut() {
+                let key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{key}.{name}")
+                };
+
+                interpolate_values(value, &key, lookup)?;
+            }
+        }
+
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Converts the strings of `value` that are numbers or booleans in all of its `schemas`
+fn coerce_values(value: &mut Value, schemas: &[&Value], root: &Value) {
+    let schemas = schemas
+        .iter()
+        .flat_map(|schema| resolve_schema(schema, root))
+        .collect::<Vec<_>>();
+
+    match value {
+        Value::String(string) => {
+            let types = schemas
+                .iter()
+                .flat_map(|schema| match &schema["type"] {
+                    Value::String(kind) => vec![kind.as_str()],
+                    Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+                    _ => vec![],
+                })
+                .collect::<Vec<_>>();
+
+            if types.contains(&"string") {
+                return;
+            }
+
+            let coerced = if types.contains(&"boolean") {
+                string.parse::<bool>().ok().map(Value::from)
+            } else if types.contains(&"integer") {
+                (string.parse::<u64>().ok().map(Value::from))
+                    .or_else(|| string.parse::<i64>().ok().map(Value::from))
+            } else if types.contains(&"number") {
+                (string.parse::<u64>().ok().map(Value::from))
+                    .or_else(|| string.parse::<f64>().ok().map(Value::from))
+            } else {
+                None
+            };
+
+            if let Some(coerced) = coerced {
+                *value = coerced;
+            }
+        }
+
+        Value::Array(values) => {
+            let items = schemas
+                .iter()
+                .filter_map(|schema| schema.get("items"))
+                .collect::<Vec<_>>();
+
+            for value in values {
+                coerce_values(value, &items, root);
+            }
+        }
+
+        Value::Object(values) => {
+            for (key, value) in values {
+                let properties = schemas
+                    .iter()
+                    .filter_map(|schema| {
+                        schema
+                            .get("properties")
+                            .and_then(|properties| properties.get(key.as_str()))
+                            .or_else(|| schema.get("additionalProperties"))
+                    })
+                    .collect::<Vec<_>>();
+
+                coerce_values(value, &properties, root);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// The schemas that `schema` refers to or combines
+fn resolve_schema<'a>(schema: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .map(|schema| resolve_schema(schema, root))
+            .unwrap_or_default();
+    }
+
+    let mut schemas = vec![schema];
+
+    for combinator in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(combined)) = schema.get(combinator) {
+            schemas.extend(
+                combined
+                    .iter()
+                    .flat_map(|schema| resolve_schema(schema, root)),
+            );
+        }
+    }
+
+    schemas
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_versions() {
+        let v1 = HopFile::deserialize(
+            PathBuf::from("hop.yml"),
+            "version: 1\nconfig:\n  project_id: project_1\n  deployment_id: deployment_1\n",
+        )
+        .unwrap();
+
+        assert_eq!(v1.deployment_ref(), Some("deployment_1"));
+        assert!(v1.deployment.is_none());
+
+        let v2 = HopFile::deserialize(
+            PathBuf::from("hop.yml"),
+            r#"
+version: 2
+deployment:
+  name: api
+  resources:
+    vcpu: 1
+    ram: 1G
+  env:
+    PORT: "8080"
+  containers: 2
+  gateways:
+    - name: web
+      type: external
+      protocol: http
+      target_port: 8080
+  health_checks:
+    - path: /health
+"#,
+        )
+        .unwrap();
+
+        let spec = v2.deployment.as_ref().unwrap();
+
+        assert_eq!(v2.deployment_ref(), Some("api"));
+        assert_eq!(spec.config.resources.ram, "1G");
+        assert_eq!(spec.containers, Some(2));
+        assert_eq!(spec.gateways[0].config.target_port, Some(8080));
+        assert_eq!(spec.health_checks[0].path, "/health");
+        assert_eq!(spec.health_checks[0].port, 8080);
+
+        // version 2 without an ID has to name the deployment
+        assert!(HopFile::deserialize(PathBuf::from("hop.yml"), "version: 2\n").is_err());
+
+        // typos are reported with their location instead of ignoring the file
+        let error = HopFile::deserialize(
+            PathBuf::from("hop.yml"),
+            "version: 2\ndeployment:\n  name: api\n  replicas: 2\n",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("unknown field `replicas`"), "{error}");
+        assert!(error.contains("line 3 column 3"), "{error}");
+        assert!(HopFile::deserialize(PathBuf::from("hop.json"), "{\"version\": 2,}").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_variables() {
+        let variables = HashMap::from([
+            ("NAME".to_string(), "api".to_string()),
+            ("VCPU".to_string(), "2".to_string()),
+            (
+                "MESSAGE".to_string(),
+                "it's \"quoted\": #1\nimage: evil".to_string(),
+            ),
+        ]);
+        let deserialize = |content: &str| {
+            HopFile::deserialize_environment(PathBuf::from("hop.yml"), content, &variables, None)
+        };
+
+        let hopfile = deserialize(
+            r#"
+version: 2
+# ${UNSET} in a comment is ignored
+deployment:
+  name: ${NAME}
+  resources:
+    vcpu: ${VCPU}
+    ram: 1G
+  env:
+    MESSAGE: ${MESSAGE}
+    PRICE: $$5
+"#,
+        )
+        .unwrap();
+
+        let spec = hopfile.deployment.as_ref().unwrap();
+
+        // values are replaced as they are, without changing the document
+        assert_eq!(spec.config.name.as_deref(), Some("api"));
+        assert_eq!(spec.config.resources.vcpu, 2.0);
+        assert_eq!(spec.config.env["MESSAGE"], variables["MESSAGE"]);
+        assert_eq!(spec.config.env["PRICE"], "$5");
+        assert!(spec.config.image.is_none());
+
+        let error = deserialize("version: 2\ndeployment:\n  name: ${UNSET}\n")
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            error.contains("`deployment.name`: `UNSET` is not set"),
+            "{error}"
+        );
+
+        // locations point at the hopfile as it is written
+        let error = deserialize(
+            "version: 2\nconfig:\n  project_id: ${MESSAGE}\ndeployment:\n  name: api\n  replicas: 2\n",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("unknown field `replicas`"), "{error}");
+        assert!(error.contains("line 5 column 3"), "{error}");
+    }
+
+    #[test]
+    fn test_json_schema_is_published() {
+        let schema = serde_json::to_string_pretty(&HopFile::json_schema()).unwrap();
+
+        assert_eq!(
+            include_str!("../../hopfile.schema.json").trim_end(),
+            schema,
+            "run `cargo run -- validate --schema > hopfile.schema.json` to update it"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_environments() {
+        let content = r#"
+version: 2
+config:
+  project_id: project_prod
+deployment:
+  name: api
+  resources:
+    vcpu: 1
+    ram: 1G
+  env:
+    PORT: "8080"
+    DEBUG: "0"
+  containers: 3
+environments:
+  staging:
+    config:
+      project_id: project_staging
+    deployment:
+      resources:
+        ram: 512M
+      env:
+        DEBUG: null
+        LOG_LEVEL: debug
+      containers: 1
+"#;
+
+        let variables = HashMap::new();
+        let base =
+            HopFile::deserialize_environment(PathBuf::from("hop.yml"), content, &variables, None)
+                .unwrap();
+
+        assert_eq!(base.config.project_id, "project_prod");
+        assert_eq!(base.deployment.as_ref().unwrap().containers, Some(3));
+
+        let staging = HopFile::deserialize_environment(
+            PathBuf::from("hop.yml"),
+            content,
+            &variables,
+            Some("staging"),
+        )
+        .unwrap();
+        let spec = staging.deployment.as_ref().unwrap();
+
+        assert_eq!(staging.environment.as_deref(), Some("staging"));
+        assert_eq!(staging.config.project_id, "project_staging");
+        assert_eq!(spec.config.name.as_deref(), Some("api"));
+        assert_eq!(spec.config.resources.vcpu, 1.0);
+        assert_eq!(spec.config.resources.ram, "512M");
+        assert_eq!(
+            spec.config.env.get("PORT").map(String::as_str),
+            Some("8080")
+        );
+        assert_eq!(
+            spec.config.env.get("LOG_LEVEL").map(String::as_str),
+            Some("debug")
+        );
+        assert!(!spec.config.env.contains_key("DEBUG"));
+        assert_eq!(spec.containers, Some(1));
+
+        assert!(HopFile::deserialize_environment(
+            PathBuf::from("hop.yml"),
+            content,
+            &variables,
+            Some("production")
+        )
+        .is_err());
+
+        // without a base config an environment has to be selected
+        let only_environments = "version: 2\nenvironments:\n  staging:\n    config:\n      deployment_id: deployment_1\n";
+
+        assert!(HopFile::deserialize_environment(
+            PathBuf::from("hop.yml"),
+            only_environments,
+            &variables,
+            None
+        )
+        .is_err());
+        assert_eq!(
+            HopFile::deserialize_environment(
+                PathBuf::from("hop.yml"),
+                only_environments,
+                &variables,
+                Some("staging")
+            )
+            .unwrap()
+            .deployment_ref(),
+            Some("deployment_1")
+        );
+    }
+
+    #[test]
+    fn test_relink_keeps_the_spec() {
+        let content = r#"
+version: 2
+config:
+  project_id: project_1
+  deployment_id: deployment_1
+deployment:
+  name: api
+  env:
+    PORT: "8080"
+environments:
+  staging:
+    config:
+      deployment_id: deployment_staging
+"#;
+        let hopfile = HopFile::deserialize(PathBuf::from("hop.yml"), content).unwrap();
+
+        let linked = hopfile.clone().relink(None, "project_2", "deployment_2");
+
+        assert_eq!(linked.version, LATEST_VERSION);
+        assert_eq!(linked.deployment_ref(), Some("deployment_2"));
+        assert_eq!(linked.config.project_id, "project_2");
+        assert_eq!(linked.deployment, hopfile.deployment);
+        assert_eq!(linked.environments, hopfile.environments);
+
+        let linked = hopfile.relink(Some("staging".to_string()), "project_2", "deployment_3");
+
+        assert_eq!(linked.config.deployment_id, "deployment_1");
+        assert_eq!(
+            linked.environments["staging"].config.deployment_id,
+            "deployment_3"
+        );
+        assert!(linked.deployment.is_some());
+    }
+
+    #[test]
+    fn test_deserialize_services() {
+        let hopfile = HopFile::deserialize(
+            PathBuf::from("repo/hop.yml"),
+            r#"
+version: 2
+services:
+  api:
+    path: services/api
+    depends_on: [db]
+  db:
+    path: services/db
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(hopfile.deployment_ref(), None);
+        assert_eq!(hopfile.services["api"].depends_on, vec!["db"]);
+        assert_eq!(
+            hopfile.service_dir(&hopfile.services["db"]),
+            PathBuf::from("repo/services/db")
+        );
+
+        // version 1 files only link a deployment
+        assert!(HopFile::deserialize(
+            PathBuf::from("hop.yml"),
+            "version: 1\nconfig:\n  project_id: project_1\n  deployment_id: deployment_1\nservices:\n  api:\n    path: api\n"
+        )
+        .is_err());
+    }
+}
@@ -6,7 +6,10 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 pub mod auth;
+pub mod cache;
+pub mod config;
 pub mod context;
+pub mod credentials;
 pub mod hopfile;
 pub mod macros;
 pub mod utils;
@@ -3,11 +3,12 @@ use std::sync::atomic::Ordering::Relaxed;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{anyhow, bail, Result};
-use async_tungstenite::tokio::connect_async_with_config;
+use anyhow::{anyhow, bail, Context, Result};
+use async_tungstenite::tokio::client_async_tls_with_connector_and_config;
 use async_tungstenite::tungstenite::protocol::WebSocketConfig;
 use async_tungstenite::tungstenite::Message;
 use futures_util::{SinkExt, StreamExt};
+use reqwest::Url;
 use serde_json::{json, Value};
 use tokio::spawn;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
@@ -15,6 +16,7 @@ use tokio::task::JoinHandle;
 use tokio::time::{interval, timeout};
 
 use super::types::{ArisuEvent, ArisuMessage, ConnectionStage, OpCode, WsStream};
+use crate::utils::network;
 
 const ARISU_URL: &str = "wss://arisu.hop.io/ws";
 
@@ -240,7 +242,21 @@ async fn connect(base_url: &str) -> Result<WsStream> {
         ..Default::default()
     };
 
-    let (stream, _) = connect_async_with_config(url, Some(config)).await?;
+    let parsed = Url::parse(&url)?;
+    let host = parsed.host_str().context("Arisu URL has no host")?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let socket = network::connect(host, port).await?;
+
+    #[cfg(not(windows))]
+    let connector = tokio_rustls::TlsConnector::from(network::tls_config()?);
+
+    #[cfg(windows)]
+    let connector = tokio_native_tls::TlsConnector::from(network::tls_connector()?);
+
+    let (stream, _) =
+        client_async_tls_with_connector_and_config(url, socket, Some(connector), Some(config))
+            .await?;
 
     Ok(stream)
 }
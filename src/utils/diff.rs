@@ -0,0 +1,175 @@
+//! Field level differences between two serializable values
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use console::style;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldChange {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl FieldChange {
+    /// Colored line like `~ resources.ram: "256M" -> "1G"`
+    pub fn format(&self) -> String {
+        match self {
+            Self::Added { path, value } => style(format!("+ {path}: {value}")).green().to_string(),
+            Self::Removed { path, value } => style(format!("- {path}: {value}")).red().to_string(),
+            Self::Changed { path, from, to } => style(format!("~ {path}: {from} -> {to}"))
+                .yellow()
+                .to_string(),
+        }
+    }
+}
+
+/// Changes needed to go from `current` to `desired`,
+/// `null` and missing fields are treated the same
+pub fn diff<T: Serialize + ?Sized>(current: &T, desired: &T) -> Result<Vec<FieldChange>> {
+    let mut changes = vec![];
+
+    diff_values(
+        "",
+        &serde_json::to_value(current)?,
+        &serde_json::to_value(desired)?,
+        &mut changes,
+    );
+
+    Ok(changes)
+}
+
+/// Every field of `value` as an addition, used for resources that will be created
+pub fn additions<T: Serialize + ?Sized>(value: &T) -> Result<Vec<FieldChange>> {
+    let mut changes = vec![];
+
+    diff_values(
+        "",
+        &Value::Null,
+        &serde_json::to_value(value)?,
+        &mut changes,
+    );
+
+    Ok(changes)
+}
+
+fn diff_values(path: &str, current: &Value, desired: &Value, changes: &mut Vec<FieldChange>) {
+    if current == desired {
+        return;
+    }
+
+    match (current, desired) {
+        (Value::Object(current), Value::Object(desired)) => {
+            let keys = current
+                .keys()
+                .chain(desired.keys())
+                .collect::<BTreeSet<_>>();
+
+            for key in keys {
+                diff_values(
+                    &join(path, key),
+                    current.get(key).unwrap_or(&Value::Null),
+                    desired.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+
+        // objects that are created or removed are listed field by field
+        (Value::Null, Value::Object(desired)) => {
+            for (key, value) in desired {
+                diff_values(&join(path, key), &Value::Null, value, changes);
+            }
+        }
+
+        (Value::Object(current), Value::Null) => {
+            for (key, value) in current {
+                diff_values(&join(path, key), value, &Value::Null, changes);
+            }
+        }
+
+        (Value::Null, value) => changes.push(FieldChange::Added {
+            path: path.to_string(),
+            value: value.clone(),
+        }),
+
+        (value, Value::Null) => changes.push(FieldChange::Removed {
+            path: path.to_string(),
+            value: value.clone(),
+        }),
+
+        (from, to) => changes.push(FieldChange::Changed {
+            path: path.to_string(),
+            from: from.clone(),
+            to: to.clone(),
+        }),
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let current = json!({
+            "name": "api",
+            "resources": { "vcpu": 0.5, "ram": "256M" },
+            "env": { "OLD": "1", "PORT": "8080" },
+            "volume": null
+        });
+        let desired = json!({
+            "name": "api",
+            "resources": { "vcpu": 1.0, "ram": "256M" },
+            "env": { "PORT": "8080", "NEW": "2" },
+            "volume": { "size": "3G" }
+        });
+
+        assert_eq!(
+            diff(&current, &desired).unwrap(),
+            vec![
+                FieldChange::Added {
+                    path: "env.NEW".to_string(),
+                    value: json!("2")
+                },
+                FieldChange::Removed {
+                    path: "env.OLD".to_string(),
+                    value: json!("1")
+                },
+                FieldChange::Changed {
+                    path: "resources.vcpu".to_string(),
+                    from: json!(0.5),
+                    to: json!(1.0)
+                },
+                FieldChange::Added {
+                    path: "volume.size".to_string(),
+                    value: json!("3G")
+                },
+            ]
+        );
+        assert!(diff(&current, &current).unwrap().is_empty());
+    }
+}
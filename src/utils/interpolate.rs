@@ -0,0 +1,94 @@
+//! `${VAR}` interpolation of hopfiles, like in docker compose files
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+/// Replaces `${VAR}`, `${VAR:-default}` and `${VAR:?error}` with the values of `lookup`.
+/// `$$` is kept as a single `$`, and references that are not variable names like
+/// `${secrets.NAME}` are left alone
+pub fn interpolate(content: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let pattern =
+        Regex::new(r"\$(?:(\$)|\{([A-Za-z_][A-Za-z0-9_]*)(?:(:-|:\?)([^}]*))?\})").unwrap();
+
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for captures in pattern.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+
+        result.push_str(&content[last..whole.start()]);
+        last = whole.end();
+
+        // escaped with `$$`
+        if captures.get(1).is_some() {
+            result.push('$');
+            continue;
+        }
+
+        let name = &captures[2];
+
+        let value = match (lookup(name), captures.get(3).map(|m| m.as_str())) {
+            (Some(value), None) => value,
+            (Some(value), Some(_)) if !value.is_empty() => value,
+            (_, Some(":-")) => captures[4].to_string(),
+
+            (_, Some(_)) if captures[4].is_empty() => {
+                bail!("`{name}` is required but it is not set")
+            }
+
+            (_, Some(_)) => bail!("`{name}` is not set: {}", &captures[4]),
+
+            (None, None) => {
+                bail!("`{name}` is not set, use `${{{name}:-}}` to default it to an empty string")
+            }
+        };
+
+        result.push_str(&value);
+    }
+
+    result.push_str(&content[last..]);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_interpolate() {
+        let variables = HashMap::from([
+            ("NAME", "api".to_string()),
+            ("RAM", "1G".to_string()),
+            ("EMPTY", String::new()),
+        ]);
+        let lookup = |name: &str| variables.get(name).cloned();
+
+        assert_eq!(
+            interpolate(
+                "name: ${NAME}-${STAGE:-dev}\nram: ${RAM:?set the ram}\nvcpu: ${VCPU:-0.5}\nempty: '${EMPTY}'",
+                lookup
+            )
+            .unwrap(),
+            "name: api-dev\nram: 1G\nvcpu: 0.5\nempty: ''"
+        );
+
+        // empty values are replaced by defaults
+        assert_eq!(interpolate("${EMPTY:-default}", lookup).unwrap(), "default");
+
+        // secrets and escaped variables are kept
+        assert_eq!(
+            interpolate("TOKEN: ${secrets.TOKEN}\nRAW: $${NAME} $$5 $$$$", lookup).unwrap(),
+            "TOKEN: ${secrets.TOKEN}\nRAW: ${NAME} $5 $$"
+        );
+
+        let error = interpolate("${MISSING}", lookup).unwrap_err();
+        assert!(error.to_string().starts_with("`MISSING` is not set"));
+
+        let error = interpolate("${MISSING:?pass the ram}", lookup).unwrap_err();
+        assert!(error.to_string().ends_with("pass the ram"));
+        assert!(interpolate("${EMPTY:?}", lookup).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Console and file logging, records are either human readable lines
+//! or one JSON object per line for log aggregation
+
+use std::collections::BTreeMap;
+use std::fmt::{Arguments, Display};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{SecondsFormat, Utc};
+use fern::colors::{Color, ColoredLevelConfig};
+use fern::{Dispatch, FormatCallback};
+use log::{Level, LevelFilter, Record};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Fields added to every JSON record, like the deployment being deployed
+static CONTEXT: Mutex<BTreeMap<&'static str, String>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_str(&format!("\"{}\"", s.to_lowercase())).map_err(|e| anyhow!(e))
+    }
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).unwrap().replace('"', "")
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LogOptions {
+    pub verbose: bool,
+    pub color: bool,
+    pub format: LogFormat,
+    /// File that receives every record, including debug ones
+    pub file: Option<PathBuf>,
+}
+
+/// Adds a field to every following JSON record, e.g. `project` or `build`
+pub fn set_context(key: &'static str, value: impl Display) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.insert(key, value.to_string());
+    }
+}
+
+/// Sets up the console logger, and the file logger if a file is passed.
+/// The console is always set up, even when the file cannot be opened
+pub fn setup(options: LogOptions) -> Result<()> {
+    let color = options.color && options.format == LogFormat::Text;
+
+    // prompts and progress bars are styled by console
+    console::set_colors_enabled(color);
+    console::set_colors_enabled_stderr(color);
+
+    let (file, error) = match options.file.as_ref().map(|path| {
+        fern::log_file(path)
+            .with_context(|| format!("Failed to open log file `{}`", path.display()))
+    }) {
+        Some(Ok(file)) => (Some(file), None),
+        Some(Err(error)) => (None, Some(error)),
+        None => (None, None),
+    };
+
+    let console_level = if options.verbose {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Info
+    };
+
+    let console = match options.format {
+        LogFormat::Text => Dispatch::new()
+            .format(move |out, message, record| format_text(out, message, record, color))
+            .level(console_level)
+            .chain(
+                Dispatch::new()
+                    .filter(|metadata| !matches!(metadata.level(), Level::Error | Level::Warn))
+                    .chain(std::io::stdout()),
+            )
+            .chain(
+                Dispatch::new()
+                    .level(LevelFilter::Warn)
+                    .chain(std::io::stderr()),
+            ),
+
+        // keep stdout free for the output of the command
+        LogFormat::Json => Dispatch::new()
+            .format(format_json)
+            .level(console_level)
+            .chain(std::io::stderr()),
+    };
+
+    let mut dispatch = Dispatch::new().level(LevelFilter::Debug).chain(console);
+
+    if let Some(file) = file {
+        dispatch = dispatch.chain(match options.format {
+            LogFormat::Text => Dispatch::new()
+                .format(|out, message, record| {
+                    out.finish(format_args!(
+                        "{} {} [{}]: {}",
+                        timestamp(),
+                        record.level().to_string().to_lowercase(),
+                        record.target(),
+                        message
+                    ))
+                })
+                .chain(file),
+
+            LogFormat::Json => Dispatch::new().format(format_json).chain(file),
+        });
+    }
+
+    dispatch.apply().ok();
+
+    error.map_or(Ok(()), Err)
+}
+
+fn format_text(out: FormatCallback, message: &Arguments, record: &Record, color: bool) {
+    let level = record.level();
+
+    let level_name = if color {
+        ColoredLevelConfig::new()
+            .info(Color::BrightCyan)
+            .error(Color::BrightRed)
+            .warn(Color::BrightYellow)
+            .debug(Color::BrightWhite)
+            .color(level)
+            .to_string()
+            .to_lowercase()
+    } else {
+        level.to_string().to_lowercase()
+    };
+
+    match level {
+        Level::Debug => out.finish(format_args!(
+            "{} [{}]: {}",
+            level_name,
+            record.target(),
+            message
+        )),
+
+        _ => out.finish(format_args!("{level_name}: {message}")),
+    }
+}
+
+fn format_json(out: FormatCallback, message: &Arguments, record: &Record) {
+    out.finish(format_args!(
+        "{}",
+        Value::Object(json_record(&message.to_string(), record))
+    ));
+}
+
+fn json_record(message: &str, record: &Record) -> Map<String, Value> {
+    let mut fields = Map::new();
+
+    fields.insert("timestamp".to_string(), timestamp().into());
+    fields.insert(
+        "level".to_string(),
+        record.level().to_string().to_lowercase().into(),
+    );
+    fields.insert("target".to_string(), record.target().into());
+    fields.insert("message".to_string(), message.into());
+
+    if let Ok(context) = CONTEXT.lock() {
+        for (key, value) in context.iter() {
+            fields.insert((*key).to_string(), value.clone().into());
+        }
+    }
+
+    fields
+}
+
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("text".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_record() {
+        set_context("build", "build_123");
+
+        let record = Record::builder()
+            .level(Level::Warn)
+            .target("hop_cli::commands::deploy")
+            .build();
+
+        let fields = json_record("Build failed", &record);
+
+        assert_eq!(fields["level"], "warn");
+        assert_eq!(fields["target"], "hop_cli::commands::deploy");
+        assert_eq!(fields["message"], "Build failed");
+        assert_eq!(fields["build"], "build_123");
+        assert!(fields["timestamp"].as_str().unwrap().ends_with('Z'));
+    }
+}
@@ -1,8 +1,16 @@
 pub mod arisu;
 pub mod browser;
 pub mod deser;
+pub mod diff;
+pub mod interpolate;
+pub mod logging;
+pub mod network;
+pub mod output;
+pub mod prompt;
+pub mod resolve;
 pub mod size;
 pub mod sudo;
+pub mod watch;
 
 use std::error::Error;
 use std::path::PathBuf;
@@ -11,8 +19,6 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use console::style;
-use fern::colors::{Color, ColoredLevelConfig};
-use log::{Level, LevelFilter};
 use ms::{__to_string__, ms};
 use serde::Serialize;
 use serde_json::Value;
@@ -49,52 +55,6 @@ pub fn clean_term() {
     }
 }
 
-pub fn logs(verbose: bool) {
-    let colors = ColoredLevelConfig::new()
-        .info(Color::BrightCyan)
-        .error(Color::BrightRed)
-        .warn(Color::BrightYellow)
-        .debug(Color::BrightWhite);
-
-    fern::Dispatch::new()
-        .format(move |out, message, record| {
-            let level = record.level();
-
-            match level {
-                Level::Debug => out.finish(format_args!(
-                    "{} [{}]: {}",
-                    colors.color(Level::Debug).to_string().to_lowercase(),
-                    record.target(),
-                    message
-                )),
-
-                level => out.finish(format_args!(
-                    "{}: {}",
-                    colors.color(level).to_string().to_lowercase(),
-                    message
-                )),
-            }
-        })
-        .level(if verbose {
-            LevelFilter::Debug
-        } else {
-            LevelFilter::Info
-        })
-        .chain(
-            fern::Dispatch::new()
-                .filter(|metadata| !matches!(metadata.level(), Level::Error | Level::Warn))
-                .chain(std::io::stdout()),
-        )
-        .chain(
-            fern::Dispatch::new()
-                .level(log::LevelFilter::Error)
-                .level(log::LevelFilter::Warn)
-                .chain(std::io::stderr()),
-        )
-        .apply()
-        .ok();
-}
-
 pub fn relative_time(date: DateTime<Utc>) -> String {
     let milis = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -105,7 +65,12 @@ pub fn relative_time(date: DateTime<Utc>) -> String {
     ms!(milis.unsigned_abs(), true)
 }
 
-pub fn ask_question_iter<T>(prompt: &str, choices: &[T], override_default: Option<T>) -> Result<T>
+pub fn ask_question_iter<T>(
+    flag: &str,
+    prompt: &str,
+    choices: &[T],
+    override_default: Option<T>,
+) -> Result<T>
 where
     T: PartialEq + Clone + Serialize + Default,
 {
@@ -119,7 +84,7 @@ where
         None => T::default(),
     };
 
-    let choice = dialoguer::Select::new()
+    let choice = prompt::Select::new(flag)
         .with_prompt(prompt)
         .default(choices.iter().position(|x| x == &to_compare).unwrap())
         .items(&choices_txt)
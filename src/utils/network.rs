@@ -0,0 +1,318 @@
+//! Proxy and certificate settings shared by the API client, Tonneru and Arisu,
+//! proxies are read from `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`
+
+use std::path::Path;
+#[cfg(not(windows))]
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use anyhow::{bail, ensure, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::Url;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+#[cfg(not(windows))]
+use tokio_rustls::rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore};
+
+const PEM_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_END: &str = "-----END CERTIFICATE-----";
+
+/// Upper bound for the response headers of a proxy
+const MAX_PROXY_RESPONSE: usize = 8 * 1024;
+
+/// Extra trusted certificates, in PEM format
+static CA_CERTS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Trust the certificates in the given PEM file on top of the built-in roots
+pub fn set_ca_cert(path: &Path) -> Result<()> {
+    let pem = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read CA certificate `{}`", path.display()))?;
+
+    let certs = split_pem(&pem);
+
+    ensure!(
+        !certs.is_empty(),
+        "No certificates found in `{}`",
+        path.display()
+    );
+
+    log::debug!(
+        "Trusting {} certificates from {}",
+        certs.len(),
+        path.display()
+    );
+
+    CA_CERTS.set(certs).ok();
+
+    Ok(())
+}
+
+pub fn ca_certs() -> &'static [String] {
+    CA_CERTS.get().map_or(&[], Vec::as_slice)
+}
+
+fn split_pem(pem: &str) -> Vec<String> {
+    let mut certs = vec![];
+    let mut rest = pem;
+
+    while let Some(start) = rest.find(PEM_BEGIN) {
+        let Some(end) = rest[start..].find(PEM_END) else {
+            break;
+        };
+
+        let end = start + end + PEM_END.len();
+
+        certs.push(rest[start..end].to_string());
+        rest = &rest[end..];
+    }
+
+    certs
+}
+
+#[cfg(not(windows))]
+fn pem_to_der(pem: &str) -> Result<Vec<u8>> {
+    let body = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+
+    BASE64
+        .decode(body.trim())
+        .context("Failed to decode CA certificate")
+}
+
+pub fn reqwest_certificates() -> Vec<reqwest::Certificate> {
+    ca_certs()
+        .iter()
+        .filter_map(|pem| match reqwest::Certificate::from_pem(pem.as_bytes()) {
+            Ok(cert) => Some(cert),
+            Err(error) => {
+                log::warn!("Ignoring invalid CA certificate: {error}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// TLS config with the built-in roots and the extra certificates
+#[cfg(not(windows))]
+pub fn tls_config() -> Result<Arc<ClientConfig>> {
+    // ref: https://github.com/rustls/hyper-rustls/blob/fcb72be6e3b0e060bfe5bc183a67c16ea56e7132/src/config.rs#L55-L69
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+
+    for pem in ca_certs() {
+        roots
+            .add(&Certificate(pem_to_der(pem)?))
+            .context("Invalid CA certificate")?;
+    }
+
+    Ok(Arc::new(
+        ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth(),
+    ))
+}
+
+/// TLS connector with the system roots and the extra certificates
+#[cfg(windows)]
+pub fn tls_connector() -> Result<native_tls::TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+
+    for pem in ca_certs() {
+        builder.add_root_certificate(
+            native_tls::Certificate::from_pem(pem.as_bytes()).context("Invalid CA certificate")?,
+        );
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Proxy to use for a TLS connection to `host`, if any
+pub fn proxy_for(host: &str) -> Option<Url> {
+    let proxy = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))?;
+
+    let no_proxy = std::env::var("NO_PROXY")
+        .or_else(|_| std::env::var("no_proxy"))
+        .unwrap_or_default();
+
+    if is_no_proxy(&no_proxy, host) {
+        return None;
+    }
+
+    // proxies are often set without a scheme
+    let proxy = if proxy.contains("://") {
+        proxy
+    } else {
+        format!("http://{proxy}")
+    };
+
+    match Url::parse(&proxy) {
+        Ok(url) => Some(url),
+        Err(error) => {
+            log::warn!("Ignoring invalid proxy `{proxy}`: {error}");
+            None
+        }
+    }
+}
+
+/// `NO_PROXY` is a comma separated list of hosts, a host also matches its subdomains
+fn is_no_proxy(no_proxy: &str, host: &str) -> bool {
+    let host = host.to_lowercase();
+
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('.').to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| entry == "*" || host == entry || host.ends_with(&format!(".{entry}")))
+}
+
+/// Opens a TCP connection to `host`, tunneled through the proxy if one is configured
+pub async fn connect(host: &str, port: u16) -> Result<TcpStream> {
+    match proxy_for(host) {
+        Some(proxy) => connect_via_proxy(&proxy, host, port).await,
+        None => Ok(TcpStream::connect((host, port)).await?),
+    }
+}
+
+async fn connect_via_proxy(proxy: &Url, host: &str, port: u16) -> Result<TcpStream> {
+    ensure!(
+        proxy.scheme() == "http",
+        "Unsupported proxy scheme `{}`, only `http` proxies can tunnel connections",
+        proxy.scheme()
+    );
+
+    let proxy_host = proxy.host_str().context("Proxy URL has no host")?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+
+    log::debug!("Tunneling {host}:{port} through proxy {proxy_host}:{proxy_port}");
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .with_context(|| format!("Failed to connect to proxy {proxy_host}:{proxy_port}"))?;
+
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+
+    if !proxy.username().is_empty() {
+        let credentials = format!(
+            "{}:{}",
+            proxy.username(),
+            proxy.password().unwrap_or_default()
+        );
+
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            BASE64.encode(credentials)
+        ));
+    }
+
+    request.push_str("\r\n");
+
+    stream.write_all(request.as_bytes()).await?;
+
+    // read byte by byte so nothing after the headers is consumed
+    let mut response = vec![];
+
+    while !response.ends_with(b"\r\n\r\n") {
+        ensure!(
+            response.len() < MAX_PROXY_RESPONSE,
+            "Proxy response is too large"
+        );
+
+        let mut byte = [0; 1];
+
+        if stream.read(&mut byte).await? == 0 {
+            bail!("Proxy closed the connection");
+        }
+
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status = response
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+
+    ensure!(
+        status == "200",
+        "Proxy refused to tunnel to {host}:{port}: {}",
+        response.lines().next().unwrap_or_default()
+    );
+
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn test_no_proxy() {
+        assert!(is_no_proxy("*", "api.hop.io"));
+        assert!(is_no_proxy("localhost, .hop.io", "arisu.hop.io"));
+        assert!(is_no_proxy("hop.io", "hop.io"));
+        assert!(!is_no_proxy("hop.io", "nothop.io"));
+        assert!(!is_no_proxy("", "api.hop.io"));
+    }
+
+    #[test]
+    fn test_split_pem() {
+        let pem = format!("junk\n{PEM_BEGIN}\nAAAA\n{PEM_END}\n{PEM_BEGIN}\nBBBB\n{PEM_END}\n");
+
+        let certs = split_pem(&pem);
+
+        assert_eq!(certs.len(), 2);
+        assert!(certs[1].contains("BBBB"));
+    }
+
+    #[tokio::test]
+    async fn test_connect_via_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Url::parse(&format!(
+            "http://user:pass@{}",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buf = vec![0; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+
+            socket
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\nhello")
+                .await
+                .unwrap();
+
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let mut stream = connect_via_proxy(&proxy, "tonneru.hop.io", 443)
+            .await
+            .unwrap();
+
+        let mut rest = String::new();
+        stream.read_to_string(&mut rest).await.unwrap();
+
+        let request = server.await.unwrap();
+
+        assert!(request.starts_with("CONNECT tonneru.hop.io:443 HTTP/1.1\r\n"));
+        assert!(request.contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"));
+        assert_eq!(rest, "hello");
+    }
+}
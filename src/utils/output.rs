@@ -0,0 +1,80 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_str(&format!("\"{}\"", s.to_lowercase())).map_err(|e| anyhow!(e))
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).unwrap().replace('"', "")
+        )
+    }
+}
+
+impl OutputFormat {
+    /// Serialize the value in a machine readable format,
+    /// returns `None` if the output should be a human readable table
+    pub fn serialize<T>(self, value: &T) -> Result<Option<String>>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            Self::Table => Ok(None),
+            Self::Json => Ok(Some(serde_json::to_string_pretty(value)?)),
+            Self::Yaml => Ok(Some(serde_yaml::to_string(value)?.trim_end().to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("YAML".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!(
+            "table".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Table
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_serialize_output() {
+        let value = json!({ "id": "deployment_123", "name": "api" });
+
+        assert_eq!(OutputFormat::Table.serialize(&value).unwrap(), None);
+        assert_eq!(
+            OutputFormat::Json.serialize(&value).unwrap().unwrap(),
+            "{\n  \"id\": \"deployment_123\",\n  \"name\": \"api\"\n}"
+        );
+        assert_eq!(
+            OutputFormat::Yaml.serialize(&value).unwrap().unwrap(),
+            "id: deployment_123\nname: api"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// this is the only place allowed to use dialoguer prompts directly
+#![allow(clippy::disallowed_types)]
+
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{ensure, Result};
+use dialoguer::Validator;
+
+use crate::errors::{ErrorKind, HopError};
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+
+/// Disable all prompts, every prompt will fail instead of waiting for input
+pub fn set_no_input(no_input: bool) {
+    NO_INPUT.store(no_input, Ordering::Relaxed);
+}
+
+pub fn is_interactive() -> bool {
+    !NO_INPUT.load(Ordering::Relaxed)
+}
+
+/// Fails if prompts are disabled, `flag` is the argument that
+/// can be used instead of the prompt
+pub fn ensure_interactive(flag: &str) -> Result<()> {
+    ensure!(
+        is_interactive(),
+        HopError::new(
+            ErrorKind::Usage,
+            format!(
+                "Missing argument `{flag}`, prompts are disabled in non-interactive mode (`--no-input`)"
+            )
+        )
+    );
+
+    Ok(())
+}
+
+pub struct Select {
+    inner: dialoguer::Select<'static>,
+    flag: String,
+}
+
+impl Select {
+    pub fn new(flag: &str) -> Self {
+        Self {
+            inner: dialoguer::Select::new(),
+            flag: flag.to_string(),
+        }
+    }
+
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.inner.with_prompt(prompt);
+        self
+    }
+
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        self.inner.items(items);
+        self
+    }
+
+    pub fn default(&mut self, val: usize) -> &mut Self {
+        self.inner.default(val);
+        self
+    }
+
+    pub fn interact(&self) -> Result<usize> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact()?)
+    }
+}
+
+pub struct MultiSelect {
+    inner: dialoguer::MultiSelect<'static>,
+    flag: String,
+}
+
+impl MultiSelect {
+    pub fn new(flag: &str) -> Self {
+        Self {
+            inner: dialoguer::MultiSelect::new(),
+            flag: flag.to_string(),
+        }
+    }
+
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.inner.with_prompt(prompt);
+        self
+    }
+
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        self.inner.items(items);
+        self
+    }
+
+    pub fn defaults(&mut self, val: &[bool]) -> &mut Self {
+        self.inner.defaults(val);
+        self
+    }
+
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact()?)
+    }
+}
+
+pub struct Confirm {
+    inner: dialoguer::Confirm<'static>,
+    flag: String,
+}
+
+impl Confirm {
+    pub fn new(flag: &str) -> Self {
+        Self {
+            inner: dialoguer::Confirm::new(),
+            flag: flag.to_string(),
+        }
+    }
+
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.inner.with_prompt(prompt);
+        self
+    }
+
+    pub fn default(&mut self, val: bool) -> &mut Self {
+        self.inner.default(val);
+        self
+    }
+
+    pub fn interact(&self) -> Result<bool> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact()?)
+    }
+
+    pub fn interact_opt(&self) -> Result<Option<bool>> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact_opt()?)
+    }
+}
+
+pub struct Input<'a, T> {
+    inner: dialoguer::Input<'a, T>,
+    flag: String,
+}
+
+impl<'a, T> Input<'a, T>
+where
+    T: Clone + ToString + FromStr + 'a,
+    <T as FromStr>::Err: Debug + ToString,
+{
+    pub fn new(flag: &str) -> Self {
+        Self {
+            inner: dialoguer::Input::new(),
+            flag: flag.to_string(),
+        }
+    }
+
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.inner.with_prompt(prompt);
+        self
+    }
+
+    pub fn default(&mut self, value: T) -> &mut Self {
+        self.inner.default(value);
+        self
+    }
+
+    pub fn show_default(&mut self, val: bool) -> &mut Self {
+        self.inner.show_default(val);
+        self
+    }
+
+    pub fn allow_empty(&mut self, val: bool) -> &mut Self {
+        self.inner.allow_empty(val);
+        self
+    }
+
+    pub fn validate_with<V>(&mut self, validator: V) -> &mut Self
+    where
+        V: Validator<T> + 'a,
+        V::Err: ToString,
+    {
+        self.inner.validate_with(validator);
+        self
+    }
+
+    pub fn interact(&mut self) -> Result<T> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact()?)
+    }
+
+    pub fn interact_text(&mut self) -> Result<T> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact_text()?)
+    }
+}
+
+pub struct Password {
+    inner: dialoguer::Password<'static>,
+    flag: String,
+}
+
+impl Password {
+    pub fn new(flag: &str) -> Self {
+        Self {
+            inner: dialoguer::Password::new(),
+            flag: flag.to_string(),
+        }
+    }
+
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.inner.with_prompt(prompt);
+        self
+    }
+
+    pub fn interact(&self) -> Result<String> {
+        ensure_interactive(&self.flag)?;
+
+        Ok(self.inner.interact()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_input_fails_fast() {
+        set_no_input(true);
+
+        let err = Select::new("<DEPLOYMENT>")
+            .with_prompt("Select a deployment")
+            .items(&["a", "b"])
+            .interact()
+            .unwrap_err();
+
+        assert!(err.to_string().contains("`<DEPLOYMENT>`"));
+        assert!(Confirm::new("--force").interact_opt().is_err());
+
+        set_no_input(false);
+    }
+}
@@ -0,0 +1,170 @@
+//! Matching of user supplied references against a list of resources,
+//! a reference is a full ID, a name or a unique prefix of an ID
+
+use anyhow::Result;
+
+use crate::errors::{ErrorKind, HopError};
+
+/// Amount of candidates listed when a reference is ambiguous
+const MAX_CANDIDATES: usize = 5;
+
+pub trait Resolvable {
+    /// Name of the resource used in errors, e.g. `deployment`
+    const KIND: &'static str;
+
+    fn id(&self) -> &str;
+
+    fn name(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Finds the resource matching `reference` by ID, name or ID prefix, in that order
+pub fn find_one<'a, T: Resolvable>(items: &'a [T], reference: &str) -> Result<&'a T> {
+    if let Some(item) = items.iter().find(|item| item.id() == reference) {
+        return Ok(item);
+    }
+
+    let by_name = items
+        .iter()
+        .filter(|item| item.name() == Some(reference))
+        .collect::<Vec<_>>();
+
+    if !by_name.is_empty() {
+        return single(by_name, reference);
+    }
+
+    // the prefix can also leave out the type, e.g. `abc` for `container_abc123`
+    let by_prefix = items
+        .iter()
+        .filter(|item| {
+            item.id().starts_with(reference)
+                || item
+                    .id()
+                    .split_once('_')
+                    .is_some_and(|(_, id)| id.starts_with(reference))
+        })
+        .collect::<Vec<_>>();
+
+    single(by_prefix, reference)
+}
+
+/// Finds a resource by its index in the list or by reference
+pub fn find_by_index_or_ref<'a, T: Resolvable>(items: &'a [T], reference: &str) -> Result<&'a T> {
+    match reference.parse::<usize>() {
+        Ok(index) => items.get(index).ok_or_else(|| {
+            HopError::new(
+                ErrorKind::NotFound,
+                format!(
+                    "There is no {} with index {index}, found {}",
+                    T::KIND,
+                    items.len()
+                ),
+            )
+            .into()
+        }),
+
+        Err(_) => find_one(items, reference),
+    }
+}
+
+/// Splits a `parent/child` reference like `api/0`
+pub fn split_reference(reference: &str) -> Option<(&str, &str)> {
+    reference
+        .split_once('/')
+        .filter(|(parent, child)| !parent.is_empty() && !child.is_empty())
+}
+
+fn single<'a, T: Resolvable>(matches: Vec<&'a T>, reference: &str) -> Result<&'a T> {
+    match matches.as_slice() {
+        [item] => Ok(item),
+
+        [] => Err(HopError::new(
+            ErrorKind::NotFound,
+            format!("No {} matches `{reference}`", T::KIND),
+        )
+        .into()),
+
+        _ => {
+            let mut candidates = matches
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|item| match item.name() {
+                    Some(name) => format!("{} ({name})", item.id()),
+                    None => item.id().to_string(),
+                })
+                .collect::<Vec<_>>();
+
+            if matches.len() > MAX_CANDIDATES {
+                candidates.push(format!("and {} more", matches.len() - MAX_CANDIDATES));
+            }
+
+            Err(HopError::new(
+                ErrorKind::Usage,
+                format!(
+                    "`{reference}` matches {} {}s: {}. Use the full ID instead",
+                    matches.len(),
+                    T::KIND,
+                    candidates.join(", ")
+                ),
+            )
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Item(&'static str, Option<&'static str>);
+
+    impl Resolvable for Item {
+        const KIND: &'static str = "item";
+
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn name(&self) -> Option<&str> {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_find_one() {
+        let items = [
+            Item("item_abc123", Some("api")),
+            Item("item_abd456", Some("web")),
+            Item("item_xyz789", Some("item_abc")),
+        ];
+
+        assert_eq!(find_one(&items, "item_abc123").unwrap().0, "item_abc123");
+        assert_eq!(find_one(&items, "web").unwrap().0, "item_abd456");
+        assert_eq!(find_one(&items, "xyz").unwrap().0, "item_xyz789");
+        assert_eq!(find_one(&items, "item_abc").unwrap().0, "item_xyz789");
+
+        let error = find_one(&items, "ab").unwrap_err();
+
+        assert_eq!(HopError::kind_of(&error), ErrorKind::Usage);
+        assert!(error
+            .to_string()
+            .contains("item_abc123 (api), item_abd456 (web)"));
+
+        assert_eq!(
+            HopError::kind_of(&find_one(&items, "nope").unwrap_err()),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_find_by_index_or_ref() {
+        let items = [Item("item_1", None), Item("item_2", None)];
+
+        assert_eq!(find_by_index_or_ref(&items, "1").unwrap().0, "item_2");
+        assert!(find_by_index_or_ref(&items, "2").is_err());
+        assert_eq!(split_reference("api/0"), Some(("api", "0")));
+        assert_eq!(split_reference("/0"), None);
+    }
+}
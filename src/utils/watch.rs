@@ -0,0 +1,318 @@
+//! Redrawing the output of a command in place, either on an interval
+//! or when Leap sends an event that could have changed it
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use console::{style, Term};
+use leap_client_rs::leap::types::Event;
+use leap_client_rs::{LeapEdge, LeapOptions};
+use serde_json::Value;
+
+use crate::commands::containers::types::ContainerEvents;
+use crate::commands::deploy::builder::types::BuildEvents;
+use crate::commands::ignite::types::RolloutEvents;
+use crate::config::LEAP_PROJECT;
+use crate::state::State;
+
+#[derive(Debug, Parser, Clone, Copy, PartialEq, Eq)]
+#[group(skip)]
+pub struct WatchOptions {
+    #[clap(short, long, help = "Keep the output up to date until interrupted")]
+    pub watch: bool,
+
+    #[clap(
+        long,
+        help = "Seconds between refreshes in watch mode",
+        default_value_t = 2,
+        value_parser = clap::value_parser!(u64).range(1..),
+        requires = "watch"
+    )]
+    pub interval: u64,
+}
+
+/// Leap events that can change the output of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEvent {
+    Rollouts,
+    Builds,
+    Containers,
+}
+
+impl WatchEvent {
+    fn matches(self, data: &Value) -> bool {
+        match self {
+            Self::Rollouts => serde_json::from_value::<RolloutEvents>(data.clone()).is_ok(),
+
+            // progress is only the build logs
+            Self::Builds => matches!(
+                serde_json::from_value::<BuildEvents>(data.clone()),
+                Ok(event) if !matches!(event, BuildEvents::BuildProgress(_))
+            ),
+
+            // metrics are sent constantly and never change a listing
+            Self::Containers => matches!(
+                serde_json::from_value::<ContainerEvents>(data.clone()),
+                Ok(event) if !matches!(event, ContainerEvents::ContainerMetricsUpdate { .. })
+            ),
+        }
+    }
+}
+
+/// Prints the lines returned by `render`, with `--watch` they are
+/// fetched again on every refresh and the changed lines are highlighted
+pub async fn render<F, Fut>(
+    state: &State,
+    options: WatchOptions,
+    events: &[WatchEvent],
+    mut render: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<String>>>,
+{
+    let lines = render().await?;
+
+    if !options.watch {
+        println!("{}", lines.join("\n"));
+
+        return Ok(());
+    }
+
+    let mut screen = Screen::new(!state.debug);
+    screen.draw(lines)?;
+
+    let mut leap = if events.is_empty() {
+        None
+    } else {
+        connect(state).await
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_secs(options.interval));
+    // the first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+
+            event = listen(&mut leap, state) => match event {
+                Some(data) if events.iter().any(|event| event.matches(&data)) => {
+                    interval.reset();
+                }
+
+                Some(_) => continue,
+
+                None => {
+                    log::debug!("Leap connection closed, refreshing on an interval");
+
+                    leap = None;
+
+                    continue;
+                }
+            },
+        }
+
+        match render().await {
+            Ok(lines) => screen.draw(lines)?,
+
+            // a single failed refresh should not stop watching
+            Err(error) => log::debug!("Failed to refresh: {error}"),
+        }
+    }
+}
+
+async fn connect(state: &State) -> Option<LeapEdge> {
+    let project_id = state.ctx.current_project()?.id;
+    let token = state.ctx.current.as_ref()?.leap_token.clone();
+
+    let leap = async {
+        let mut leap = LeapEdge::new(LeapOptions {
+            token: Some(&token),
+            project: &std::env::var("LEAP_PROJECT").unwrap_or_else(|_| LEAP_PROJECT.to_string()),
+            ws_url: &std::env::var("LEAP_WS_URL")
+                .unwrap_or_else(|_| LeapOptions::default().ws_url.to_string()),
+        })
+        .await?;
+
+        leap.channel_subscribe(&project_id).await?;
+
+        anyhow::Ok(leap)
+    };
+
+    match leap.await {
+        Ok(leap) => Some(leap),
+
+        Err(error) => {
+            log::debug!("Failed to connect to Leap, refreshing on an interval: {error}");
+
+            None
+        }
+    }
+}
+
+/// Waits for the next message on the project channel, `None` once the connection is closed
+async fn listen(leap: &mut Option<LeapEdge>, state: &State) -> Option<Value> {
+    let Some(connection) = leap else {
+        return std::future::pending().await;
+    };
+
+    let project_id = state.ctx.current_project().map(|project| project.id);
+
+    while let Some(event) = connection.listen().await {
+        if let Event::Message(capsuled) = event {
+            if capsuled.channel.is_some() && capsuled.channel != project_id {
+                continue;
+            }
+
+            if let Ok(data) = serde_json::to_value(capsuled.data) {
+                return Some(data);
+            }
+        }
+    }
+
+    None
+}
+
+struct Screen {
+    term: Term,
+    /// Replace the previous output instead of printing below it
+    in_place: bool,
+    previous: Vec<String>,
+}
+
+impl Screen {
+    fn new(in_place: bool) -> Self {
+        let term = Term::stdout();
+        let in_place = in_place && term.is_term();
+
+        Self {
+            term,
+            in_place,
+            previous: vec![],
+        }
+    }
+
+    fn draw(&mut self, lines: Vec<String>) -> Result<()> {
+        let lines = split_lines(&lines);
+
+        if !self.previous.is_empty() && lines == self.previous {
+            return Ok(());
+        }
+
+        if !self.in_place {
+            writeln!(self.term, "{}", lines.join("\n"))?;
+
+            self.previous = lines;
+
+            return Ok(());
+        }
+
+        // lines that wrap would break clearing the previous output
+        let width = self.term.size().1 as usize;
+
+        let output = highlight_changes(&self.previous, &lines)
+            .into_iter()
+            .map(|(line, changed)| {
+                let line = console::truncate_str(&line, width, "").to_string();
+
+                if changed {
+                    style(line).yellow().to_string()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.term.clear_last_lines(self.previous.len())?;
+        writeln!(self.term, "{}", output.join("\n"))?;
+
+        self.previous = lines;
+
+        Ok(())
+    }
+}
+
+/// Serialized output is a single multi-line string, every row
+/// of the terminal has to be its own line to be cleared and truncated
+fn split_lines(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .flat_map(|line| line.lines())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Pairs every line with whether it is new since the previous output,
+/// nothing is highlighted on the first draw
+fn highlight_changes(previous: &[String], lines: &[String]) -> Vec<(String, bool)> {
+    let previous_lines = previous.iter().collect::<HashSet<_>>();
+
+    lines
+        .iter()
+        .map(|line| {
+            (
+                line.clone(),
+                !previous.is_empty() && !previous_lines.contains(line),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_split_lines() {
+        let document = serde_json::to_string_pretty(&json!({ "name": "api" })).unwrap();
+
+        assert_eq!(
+            split_lines(&[document, "NAME".to_string()]),
+            vec!["{", "  \"name\": \"api\"", "}", "NAME"]
+        );
+    }
+
+    #[test]
+    fn test_highlight_changes() {
+        let previous = vec!["ID\tSTATE".to_string(), "a\tpending".to_string()];
+        let lines = vec![
+            "ID\tSTATE".to_string(),
+            "a\trunning".to_string(),
+            "b\tpending".to_string(),
+        ];
+
+        assert_eq!(
+            highlight_changes(&previous, &lines)
+                .into_iter()
+                .map(|(_, changed)| changed)
+                .collect::<Vec<_>>(),
+            vec![false, true, true]
+        );
+        assert!(highlight_changes(&[], &lines)
+            .iter()
+            .all(|(_, changed)| !changed));
+    }
+
+    #[test]
+    fn test_watch_event_matches() {
+        let metrics = json!({
+            "e": "CONTAINER_METRICS_UPDATE",
+            "d": {
+                "container_id": "container_1",
+                "metrics": { "cpu_usage_percent": 1.0, "memory_usage_bytes": 1024 }
+            }
+        });
+        let update = json!({ "e": "CONTAINER_UPDATE", "d": { "container": {} } });
+
+        assert!(WatchEvent::Containers.matches(&update));
+        assert!(!WatchEvent::Containers.matches(&metrics));
+        assert!(!WatchEvent::Rollouts.matches(&update));
+    }
+}